@@ -3,83 +3,245 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use eframe::egui::{self, Color32, ColorImage, Pos2, TextureHandle};
+use eframe::egui::{self, Color32, ColorImage, Pos2, Rect, Stroke, TextureHandle, Vec2};
+use resvg::usvg;
 
-use crate::board::{coord_margin, draw_checkerboard, draw_coordinates, draw_grid, draw_hoshi_points};
-use crate::config::Config;
-use crate::pieces::{draw_hand, draw_pieces};
-use crate::sfen::{detect_board_size, is_chess, parse_hand, parse_sfen, Hand, Piece};
+use crate::board::{
+    draw_background_image, draw_castling_markers, draw_checkerboard, draw_coordinates, draw_grid,
+    draw_hoshi_points, draw_intersections, draw_promotion_zones, CoordinateOptions, SquareFill,
+};
+use crate::compare::Differences;
+use crate::editor::{
+    add_to_hand, flip_owner, palette_keys, position_sfen, side_to_move, take_from_hand, toggle_promotion,
+    toggle_side, Editor, Spot,
+};
+use crate::config::{Appearance, ChessConfig, Config, ShogiConfig, XiangqiConfig};
+use crate::layout::{split_compare_area, BoardLayout};
+use crate::pieces::{draw_hand, draw_pieces, HandPlacement, PieceColors, TrayColors};
+use crate::sfen::{is_gote, opposite_side_key, parse_castling, parse_sfen, Hand, Piece};
+use crate::textures::{
+    self, image_to_color_image, is_svg, load_svg, rasterize_svg, rotate_180, slice_sprite,
+};
+use crate::variant::{parse_position_hand, BoardStyle, Family, SideColors, Variant};
 
-/// Main application state
-pub struct SfenApp {
+/// Size of edit mode palette pieces at scale 1.0
+const PALETTE_PIECE_SIZE: f32 = 36.0;
+
+/// Fill of squares and hand pieces that differ in compare mode
+const DIFFERENCE_HIGHLIGHT: Color32 = Color32::from_rgba_premultiplied(110, 45, 0, 110);
+
+/// Colors used to draw the board
+#[derive(Clone, Copy)]
+struct Palette {
+    background: Color32,
+    grid: Color32,
+    promotion_zone: Color32,
+    text: Color32,
+    light_square: Color32,
+    dark_square: Color32,
+    pieces: PieceColors,
+    tray: TrayColors,
+}
+
+impl Palette {
+    fn new(shogi: &ShogiConfig, chess: &ChessConfig, xiangqi: &XiangqiConfig, variant: &dyn Variant) -> Self {
+        let (background, grid, text) = match variant.family() {
+            Family::Chess => (chess.light_square_color(), chess.dark_square_color(), chess.text_color()),
+            Family::Shogi => (shogi.background_color(), shogi.grid_color(), shogi.text_color()),
+            Family::Xiangqi => (xiangqi.background_color(), xiangqi.grid_color(), xiangqi.text_color()),
+        };
+        // Chess pockets sit in trays styled like the board squares
+        let tray = match variant.family() {
+            Family::Chess => TrayColors {
+                fill: chess.dark_square_color().gamma_multiply(0.35),
+                border: chess.dark_square_color(),
+                text,
+            },
+            Family::Shogi | Family::Xiangqi => TrayColors {
+                fill: Color32::TRANSPARENT,
+                border: grid,
+                text,
+            },
+        };
+        let (first_side, second_side) = match variant.side_colors() {
+            SideColors::RedBlack => (xiangqi.red_piece_color(), xiangqi.black_piece_color()),
+            SideColors::BlueRed => (xiangqi.blue_piece_color(), xiangqi.red_piece_color()),
+        };
+
+        Self {
+            background,
+            grid,
+            promotion_zone: shogi.promotion_zone_color(),
+            text,
+            light_square: chess.light_square_color(),
+            dark_square: chess.dark_square_color(),
+            pieces: PieceColors {
+                text,
+                first_side,
+                second_side,
+                disc: xiangqi.piece_background_color(),
+            },
+            tray,
+        }
+    }
+}
+
+/// A parsed position ready for drawing
+struct Position {
     board: Vec<Vec<Option<Piece>>>,
     hand: Hand,
-    board_size: usize,
+    /// Rooks with castling rights, as (row, col) squares (chess only)
+    castling: Vec<(usize, usize)>,
+    /// Short note shown in the corner, e.g. the handicap name
+    status: Option<String>,
+    /// Side to move ("b" or "w"), changed in edit mode
+    side: String,
+    /// The SFEN/FEN as read, for the fields the editor keeps
+    sfen: String,
+}
+
+impl Position {
+    fn new(sfen: &str, status: Option<String>, variant: &dyn Variant) -> Self {
+        let (files, ranks) = variant.dimensions();
+        let mut board = parse_sfen(sfen, files, ranks).unwrap_or_else(|e| {
+            eprintln!("Invalid position: {}", e);
+            vec![vec![None; files]; ranks]
+        });
+        for piece in board.iter_mut().flatten().flatten() {
+            piece.sfen_key = variant.normalize_key(&piece.sfen_key);
+        }
+        let hand = parse_position_hand(variant, sfen);
+        let castling = match variant.family() {
+            Family::Chess => parse_castling(sfen, &board),
+            Family::Shogi | Family::Xiangqi => Vec::new(),
+        };
+
+        Self {
+            board,
+            hand,
+            castling,
+            status,
+            side: side_to_move(variant, sfen),
+            sfen: sfen.to_string(),
+        }
+    }
+
+    fn has_hand_pieces(&self) -> bool {
+        !self.hand.sente.is_empty() || !self.hand.gote.is_empty()
+    }
+}
+
+/// Main application state
+pub struct SfenApp {
+    /// Positions from the clipboard, paged with PageUp/PageDown
+    positions: Vec<Position>,
+    /// Index of the position shown
+    current: usize,
+    /// Whether all positions are shown as a grid of thumbnails
+    overview: bool,
+    /// Differences between the first two positions, shown side by side (compare mode)
+    comparison: Option<Differences>,
+    /// Board editor state, while edit mode is on
+    editor: Option<Editor>,
+    /// Whether hand space is reserved (drop variants with pieces in any position's hand)
+    has_hand: bool,
+    dimensions: (usize, usize),
+    variant: &'static dyn Variant,
     textures: HashMap<String, TextureHandle>,
+    svg_pieces: HashMap<String, usvg::Tree>,
+    /// SVG pieces derived by rotation: (derived key, source key)
+    rotated_svg_pieces: Vec<(String, String)>,
+    svg_raster_size: u32,
+    background_texture: Option<TextureHandle>,
+    light_square_texture: Option<TextureHandle>,
+    dark_square_texture: Option<TextureHandle>,
     assets_path: PathBuf,
     config: Config,
-    background_color: Color32,
-    grid_color: Color32,
-    text_color: Color32,
-    light_square_color: Color32,
-    dark_square_color: Color32,
-    is_chess: bool,
+    light_palette: Palette,
+    dark_palette: Palette,
     textures_loaded: bool,
     frame_count: u32,
 }
 
 impl SfenApp {
-    /// Create a new application with the given SFEN, assets path, and config
-    pub fn new(sfen: String, assets_path: PathBuf, config: Config) -> Self {
-        let board_size = detect_board_size(&sfen);
-        let board = parse_sfen(&sfen, board_size);
-        let hand = parse_hand(&sfen);
-        let is_chess_board = is_chess(board_size);
-
-        let (background_color, grid_color, text_color) = if is_chess_board {
-            (
-                config.chess.light_square_color(),
-                Color32::TRANSPARENT,
-                config.chess.text_color(),
-            )
-        } else {
-            (
-                config.shogi.background_color(),
-                config.shogi.grid_color(),
-                config.shogi.text_color(),
-            )
-        };
+    /// Create a new application with the given positions (SFEN and status note), variant,
+    /// differences (compare mode), assets path, and config
+    ///
+    /// With differences, the first two positions are shown side by side (compare mode).
+    pub fn new(
+        positions: Vec<(String, Option<String>)>,
+        variant: &'static dyn Variant,
+        comparison: Option<Differences>,
+        assets_path: PathBuf,
+        config: Config,
+    ) -> Self {
+        let dimensions = variant.dimensions();
+        let positions: Vec<Position> = positions
+            .into_iter()
+            .map(|(sfen, status)| Position::new(&sfen, status, variant))
+            .collect();
+        let has_hand = variant.has_hand() && positions.iter().any(Position::has_hand_pieces);
 
-        let light_square_color = config.chess.light_square_color();
-        let dark_square_color = config.chess.dark_square_color();
+        let light_palette = Palette::new(&config.shogi, &config.chess, &config.xiangqi, variant);
+        let dark = &config.dark;
+        let dark_palette = Palette::new(&dark.shogi, &dark.chess, &dark.xiangqi, variant);
 
         Self {
-            board,
-            hand,
-            board_size,
+            positions,
+            current: 0,
+            overview: false,
+            comparison,
+            editor: None,
+            has_hand,
+            dimensions,
+            variant,
             textures: HashMap::new(),
+            svg_pieces: HashMap::new(),
+            rotated_svg_pieces: Vec::new(),
+            svg_raster_size: 0,
+            background_texture: None,
+            light_square_texture: None,
+            dark_square_texture: None,
             assets_path,
             config,
-            background_color,
-            grid_color,
-            text_color,
-            light_square_color,
-            dark_square_color,
-            is_chess: is_chess_board,
+            light_palette,
+            dark_palette,
             textures_loaded: false,
             frame_count: 0,
         }
     }
 
-    /// Load textures from configured piece files (skipped for chess)
+    /// Load textures from the variant's bundled piece set and configured piece files
     fn load_textures(&mut self, ctx: &egui::Context) {
-        if self.textures_loaded || self.is_chess {
-            self.textures_loaded = true;
+        if self.textures_loaded {
             return;
         }
 
+        let mut images: HashMap<String, ColorImage> = HashMap::new();
+
+        for (sfen_key, bytes) in self.variant.default_pieces() {
+            if let Ok(img) = image::load_from_memory(bytes) {
+                images.insert(sfen_key.to_string(), image_to_color_image(img));
+            }
+        }
+
+        let (assets, sprite_sheet, pieces, auto_rotate) = match self.variant.family() {
+            Family::Chess => {
+                let chess = &self.config.chess;
+                (&chess.assets_path, &chess.sprite_sheet, &chess.pieces, false)
+            }
+            Family::Shogi => {
+                let shogi = &self.config.shogi;
+                (&shogi.assets_path, &shogi.sprite_sheet, &shogi.pieces, shogi.auto_rotate())
+            }
+            Family::Xiangqi => {
+                let xiangqi = &self.config.xiangqi;
+                (&xiangqi.assets_path, &xiangqi.sprite_sheet, &xiangqi.pieces, false)
+            }
+        };
+
         // Determine base path for piece images
-        let base_path = if let Some(ref assets) = self.config.shogi.assets_path {
+        let base_path = if let Some(assets) = assets {
             let assets_path = PathBuf::from(assets);
             if assets_path.is_absolute() {
                 assets_path
@@ -89,27 +251,490 @@ impl SfenApp {
         } else {
             self.assets_path.clone()
         };
-
-        for (sfen_key, piece_path) in &self.config.shogi.pieces {
-            let piece_path = PathBuf::from(piece_path);
-            let path = if piece_path.is_absolute() {
-                piece_path
+        let resolve = |path: &str| {
+            let path = PathBuf::from(path);
+            if path.is_absolute() {
+                path
             } else {
-                base_path.join(piece_path)
-            };
+                base_path.join(path)
+            }
+        };
+
+        // Board images; the configured colors remain as fallback if these fail to load
+        let load_board_image = |path: &Option<String>| {
+            let img = image::open(resolve(path.as_ref()?)).ok()?;
+            Some(image_to_color_image(img))
+        };
+        match self.variant.family() {
+            Family::Chess => {
+                self.light_square_texture = load_board_image(&self.config.chess.light_square_image)
+                    .map(|img| textures::upload(ctx, "light_square", img));
+                self.dark_square_texture = load_board_image(&self.config.chess.dark_square_image)
+                    .map(|img| textures::upload(ctx, "dark_square", img));
+            }
+            Family::Shogi => {
+                self.background_texture = load_board_image(&self.config.shogi.background_image)
+                    .map(|img| textures::upload_repeating(ctx, "background", img));
+            }
+            Family::Xiangqi => {}
+        }
 
-            if let Ok(img) = image::open(&path) {
-                let rgba = img.to_rgba8();
-                let size = [rgba.width() as usize, rgba.height() as usize];
-                let pixels = rgba.into_raw();
-                let color_image = ColorImage::from_rgba_unmultiplied(size, &pixels);
-                let texture = ctx.load_texture(sfen_key, color_image, Default::default());
-                self.textures.insert(sfen_key.clone(), texture);
+        // Sprite sheet first, so individually configured pieces take precedence
+        if let Some(sheet_config) = sprite_sheet {
+            if let Ok(sheet) = image::open(resolve(&sheet_config.path)) {
+                let (rows, cols) = sheet_config.grid();
+                for (row, keys) in sheet_config.layout.iter().enumerate() {
+                    for (col, sfen_key) in keys.iter().enumerate() {
+                        if sfen_key.is_empty() {
+                            continue;
+                        }
+                        if let Some(img) = slice_sprite(&sheet, rows, cols, row, col) {
+                            images.insert(sfen_key.clone(), image_to_color_image(img));
+                        }
+                    }
+                }
             }
         }
 
+        for (sfen_key, piece_path) in pieces {
+            let path = resolve(piece_path);
+
+            // SVGs are rasterized later, once the cell size is known
+            if is_svg(&path) {
+                if let Some(tree) = load_svg(&path) {
+                    images.remove(sfen_key);
+                    self.svg_pieces.insert(sfen_key.clone(), tree);
+                }
+            } else if let Ok(img) = image::open(&path) {
+                self.svg_pieces.remove(sfen_key);
+                images.insert(sfen_key.clone(), image_to_color_image(img));
+            }
+        }
+
+        // Derive missing pieces of one side by rotating the other side's images
+        if auto_rotate {
+            let has_image = |key: &str| images.contains_key(key) || self.svg_pieces.contains_key(key);
+            let mut rotated = Vec::new();
+            for sfen_key in images.keys().chain(self.svg_pieces.keys()) {
+                let opposite = opposite_side_key(sfen_key);
+                if opposite != *sfen_key && !has_image(&opposite) {
+                    rotated.push((opposite, sfen_key.clone()));
+                }
+            }
+
+            for (sfen_key, source_key) in rotated {
+                if let Some(image) = images.get(&source_key) {
+                    images.insert(sfen_key, rotate_180(image));
+                } else {
+                    self.rotated_svg_pieces.push((sfen_key, source_key));
+                }
+            }
+        }
+
+        for (sfen_key, image) in images {
+            let texture = textures::upload(ctx, &sfen_key, image);
+            self.textures.insert(sfen_key, texture);
+        }
+
         self.textures_loaded = true;
     }
+
+    /// Rasterize SVG pieces at the given pixel size (no-op if unchanged)
+    fn rasterize_svg_pieces(&mut self, ctx: &egui::Context, size: u32) {
+        if self.svg_pieces.is_empty() || size == self.svg_raster_size {
+            return;
+        }
+
+        for (sfen_key, tree) in &self.svg_pieces {
+            if let Some(image) = rasterize_svg(tree, size) {
+                self.textures.insert(sfen_key.clone(), textures::upload(ctx, sfen_key, image));
+            }
+        }
+
+        for (sfen_key, source_key) in &self.rotated_svg_pieces {
+            let image = self.svg_pieces.get(source_key).and_then(|tree| rasterize_svg(tree, size));
+            if let Some(image) = image {
+                let image = rotate_180(&image);
+                self.textures.insert(sfen_key.clone(), textures::upload(ctx, sfen_key, image));
+            }
+        }
+
+        self.svg_raster_size = size;
+    }
+
+    /// Handle paging keys: PageUp/PageDown (and Home/End) step through the positions,
+    /// G toggles the thumbnail grid
+    fn handle_navigation(&mut self, ctx: &egui::Context) {
+        if self.comparison.is_some() || self.editor.is_some() {
+            return;
+        }
+        let last = self.positions.len().saturating_sub(1);
+        ctx.input(|i| {
+            if i.key_pressed(egui::Key::PageDown) {
+                self.current = (self.current + 1).min(last);
+            }
+            if i.key_pressed(egui::Key::PageUp) {
+                self.current = self.current.saturating_sub(1);
+            }
+            if i.key_pressed(egui::Key::Home) {
+                self.current = 0;
+            }
+            if i.key_pressed(egui::Key::End) {
+                self.current = last;
+            }
+            if i.key_pressed(egui::Key::G) && last > 0 {
+                self.overview = !self.overview;
+            }
+        });
+    }
+
+    /// Draw one position (board, coordinates, pieces, and hands) fitted into `area`,
+    /// highlighting the given differences
+    fn draw_position(
+        &self,
+        painter: &egui::Painter,
+        area: Rect,
+        position: &Position,
+        palette: &Palette,
+        differences: Option<&Differences>,
+    ) {
+        let layout = self.board_layout(area);
+        let cell_size = layout.cell_size;
+        let offset = layout.offset;
+
+        let board_style = self.variant.board_style();
+        if board_style == BoardStyle::Intersections {
+            draw_intersections(
+                painter,
+                offset,
+                cell_size,
+                self.dimensions,
+                palette.grid,
+                self.variant.river(),
+                self.variant.palaces(),
+            );
+        } else if board_style == BoardStyle::Checkerboard {
+            draw_checkerboard(
+                painter,
+                offset,
+                cell_size,
+                self.dimensions,
+                SquareFill {
+                    color: palette.light_square,
+                    texture: self.light_square_texture.as_ref().map(TextureHandle::id),
+                },
+                SquareFill {
+                    color: palette.dark_square,
+                    texture: self.dark_square_texture.as_ref().map(TextureHandle::id),
+                },
+            );
+            if self.config.chess.show_castling_markers() {
+                draw_castling_markers(painter, offset, cell_size, &position.castling, palette.text);
+            }
+        } else if self.variant.family() == Family::Chess {
+            // Makruk and shatranj: plain grid styled from the chess section
+            draw_grid(
+                painter,
+                offset,
+                cell_size,
+                self.dimensions,
+                palette.grid,
+                self.config.chess.border_width(),
+            );
+        } else {
+            let shogi = &self.config.shogi;
+            if shogi.show_promotion_zones() {
+                draw_promotion_zones(
+                    painter,
+                    offset,
+                    cell_size,
+                    self.dimensions,
+                    self.variant.promotion_ranks(),
+                    palette.promotion_zone,
+                );
+            }
+            draw_grid(
+                painter,
+                offset,
+                cell_size,
+                self.dimensions,
+                palette.grid,
+                shogi.border_width(),
+            );
+            if shogi.show_hoshi() {
+                draw_hoshi_points(painter, offset, cell_size, self.variant.hoshi(), palette.grid);
+            }
+        }
+
+        draw_coordinates(
+            painter,
+            offset,
+            cell_size,
+            self.dimensions,
+            palette.text,
+            CoordinateOptions {
+                style: self.config.coordinate_style(self.variant.notation()),
+                notation: self.variant.notation(),
+                all_sides: self.config.coordinates_all_sides,
+            },
+        );
+        if let Some(differences) = differences {
+            for &(row, col) in &differences.squares {
+                let min = offset + Vec2::new(col as f32, row as f32) * cell_size;
+                painter.rect_filled(Rect::from_min_size(min, Vec2::splat(cell_size)), 0.0, DIFFERENCE_HIGHLIGHT);
+            }
+        }
+        draw_pieces(
+            painter,
+            offset,
+            cell_size,
+            &position.board,
+            &self.textures,
+            palette.pieces,
+            self.variant,
+        );
+
+        // Draw pieces in hand (shogi hands or crazyhouse pockets)
+        if self.shows_hands() {
+            let (hand_cell_size, gote_hand_pos, sente_hand_pos) = hand_origins(&layout, &position.hand);
+
+            // Gote's hand - upper left (within left hand_width area)
+            if !position.hand.gote.is_empty() {
+                highlight_hand(painter, gote_hand_pos, hand_cell_size, &position.hand.gote, differences);
+                let placement = HandPlacement {
+                    top_left: gote_hand_pos,
+                    cell_size: hand_cell_size,
+                    gote: true,
+                };
+                draw_hand(painter, placement, &position.hand.gote, &self.textures, palette.tray, self.variant);
+            }
+
+            // Sente's hand - lower right (within right hand_width area)
+            if !position.hand.sente.is_empty() {
+                highlight_hand(painter, sente_hand_pos, hand_cell_size, &position.hand.sente, differences);
+                let placement = HandPlacement {
+                    top_left: sente_hand_pos,
+                    cell_size: hand_cell_size,
+                    gote: false,
+                };
+                draw_hand(painter, placement, &position.hand.sente, &self.textures, palette.tray, self.variant);
+            }
+        }
+    }
+
+    /// Whether hand space is laid out: when a position has pieces in hand, or while editing
+    /// a drop variant
+    fn shows_hands(&self) -> bool {
+        self.has_hand || (self.editor.is_some() && self.variant.has_hand())
+    }
+
+    /// Placement of a board fitted into `area`
+    fn board_layout(&self, area: Rect) -> BoardLayout {
+        BoardLayout::new(area, self.dimensions, self.variant.coord_margin(), self.shows_hands())
+    }
+
+    /// Cell size of a board fitted into `area`
+    fn cell_size(&self, area: Rect) -> f32 {
+        self.board_layout(area).cell_size
+    }
+
+    /// Handle edit mode keys: E toggles edit mode, T changes the side to move, C copies the
+    /// position as SFEN/FEN
+    fn handle_edit_keys(&mut self, ctx: &egui::Context) {
+        if self.comparison.is_some() || self.overview {
+            return;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::E)) {
+            self.editor = match self.editor {
+                Some(_) => None,
+                None => Some(Editor::default()),
+            };
+        }
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        let position = &mut self.positions[self.current];
+
+        if ctx.input(|i| i.key_pressed(egui::Key::T)) {
+            position.side = toggle_side(&position.side).to_string();
+            editor.notice = None;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::C)) {
+            let sfen = position_sfen(self.variant, &position.board, &position.hand, &position.side, &position.sfen);
+            println!("{}", sfen);
+            ctx.copy_text(sfen.clone());
+            editor.notice = Some(format!("Copied {}", sfen));
+        }
+    }
+
+    /// Where a point falls: a board square, or a hand area of a drop variant
+    fn spot_at(&self, layout: &BoardLayout, pos: Pos2) -> Option<Spot> {
+        let (files, ranks) = self.dimensions;
+        let relative = (pos - layout.offset) / layout.cell_size;
+        if relative.x >= 0.0 && relative.y >= 0.0 && relative.x < files as f32 && relative.y < ranks as f32 {
+            return Some(Spot::Square(relative.y as usize, relative.x as usize));
+        }
+        if !self.variant.has_hand() {
+            return None;
+        }
+        if pos.x < layout.offset.x - layout.coord_space {
+            Some(Spot::Hand { gote: true })
+        } else if pos.x > layout.right_hand_x() {
+            Some(Spot::Hand { gote: false })
+        } else {
+            None
+        }
+    }
+
+    /// The hand piece drawn at a point, as its key and whether it is in gote's hand
+    fn hand_piece_at(&self, layout: &BoardLayout, hand: &Hand, pos: Pos2) -> Option<(String, bool)> {
+        let (cell_size, gote_origin, sente_origin) = hand_origins(layout, hand);
+        [(gote_origin, &hand.gote, true), (sente_origin, &hand.sente, false)]
+            .into_iter()
+            .find_map(|(origin, pieces, gote)| {
+                let rect = Rect::from_min_size(origin, Vec2::new(cell_size, cell_size * pieces.len() as f32));
+                if !rect.contains(pos) {
+                    return None;
+                }
+                let index = ((pos.y - origin.y) / cell_size) as usize;
+                pieces.get(index).map(|(key, _)| (key.clone(), gote))
+            })
+    }
+
+    /// Apply pointer edits: drag pieces between squares and hands (dropping elsewhere removes
+    /// them), click a square to place the palette piece, right-click to promote or demote,
+    /// and Shift+right-click to give the piece to the other side
+    fn handle_edit_pointer(&mut self, ctx: &egui::Context, response: &egui::Response, layout: &BoardLayout) {
+        let position = &self.positions[self.current];
+        let press = ctx.input(|i| i.pointer.press_origin());
+        let pointer = response.interact_pointer_pos().or_else(|| ctx.input(|i| i.pointer.latest_pos()));
+        let press_spot = press.and_then(|pos| self.spot_at(layout, pos));
+        let pointer_spot = pointer.and_then(|pos| self.spot_at(layout, pos));
+        let hand_piece = press.and_then(|pos| self.hand_piece_at(layout, &position.hand, pos));
+        let shift = ctx.input(|i| i.modifiers.shift);
+
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        let position = &mut self.positions[self.current];
+        let mut edited = false;
+
+        if response.drag_started_by(egui::PointerButton::Primary) {
+            editor.dragged = match (press_spot, hand_piece) {
+                (Some(spot @ Spot::Square(row, col)), _) => {
+                    position.board[row][col].take().map(|piece| (piece.sfen_key, spot))
+                }
+                (_, Some((key, gote))) => {
+                    take_from_hand(&mut position.hand, &key, gote).map(|key| (key, Spot::Hand { gote }))
+                }
+                _ => None,
+            };
+        }
+        if response.drag_stopped() {
+            if let Some((key, origin)) = editor.dragged.take() {
+                let accepted = match pointer_spot {
+                    Some(Spot::Square(row, col)) => {
+                        position.board[row][col] = Some(Piece { sfen_key: key.clone() });
+                        true
+                    }
+                    Some(Spot::Hand { gote }) => add_to_hand(self.variant, &mut position.hand, &key, gote),
+                    None => true,
+                };
+                // A piece refused by a hand goes back where it came from
+                if !accepted {
+                    match origin {
+                        Spot::Square(row, col) => position.board[row][col] = Some(Piece { sfen_key: key }),
+                        Spot::Hand { gote } => {
+                            add_to_hand(self.variant, &mut position.hand, &key, gote);
+                        }
+                    }
+                }
+                edited = true;
+            }
+        }
+        if response.clicked() {
+            if let (Some(Spot::Square(row, col)), Some(brush)) = (pointer_spot, &editor.brush) {
+                position.board[row][col] = Some(Piece { sfen_key: brush.clone() });
+                edited = true;
+            }
+        }
+        if response.secondary_clicked() {
+            if let Some(Spot::Square(row, col)) = pointer_spot {
+                if let Some(piece) = position.board[row][col].as_mut() {
+                    let changed = if shift {
+                        Some(flip_owner(&piece.sfen_key))
+                    } else {
+                        toggle_promotion(self.variant, &piece.sfen_key)
+                    };
+                    if let Some(key) = changed {
+                        piece.sfen_key = key;
+                        edited = true;
+                    }
+                }
+            }
+        }
+
+        if edited {
+            editor.notice = None;
+            if self.variant.family() == Family::Chess {
+                position.castling = parse_castling(&position.sfen, &position.board);
+            }
+        }
+    }
+
+    /// Draw the edit mode palette and help line in a bottom panel
+    fn show_palette(&mut self, ctx: &egui::Context, palette: &Palette) {
+        let Some(editor) = self.editor.as_mut() else {
+            return;
+        };
+        let position = &self.positions[self.current];
+        let textures = &self.textures;
+        let variant = self.variant;
+        let button_size = Vec2::splat(PALETTE_PIECE_SIZE * self.config.scale_factor());
+
+        let frame = egui::Frame::NONE.fill(palette.background).inner_margin(8.0);
+        egui::TopBottomPanel::bottom("palette").frame(frame).show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for key in palette_keys(variant) {
+                    let button = match textures.get(&key) {
+                        Some(texture) => egui::Button::image(egui::Image::new(texture).fit_to_exact_size(button_size)),
+                        None => {
+                            let side = if is_gote(&key) { "△" } else { "▲" };
+                            egui::Button::new(format!("{}{}", side, variant.piece_label(&key))).min_size(button_size)
+                        }
+                    };
+                    let selected = editor.brush.as_ref() == Some(&key);
+                    if ui.add(button.selected(selected)).clicked() {
+                        editor.brush = if selected { None } else { Some(key) };
+                    }
+                }
+            });
+
+            let help = format!(
+                "Edit mode ({} to move). Drag pieces, click a square to place the selected piece, \
+                 right-click to promote, Shift+right-click to change sides. T: side to move, C: copy, E: done",
+                position.side
+            );
+            ui.colored_label(palette.text, help);
+            if let Some(notice) = &editor.notice {
+                ui.colored_label(palette.text, notice);
+            }
+        });
+    }
+
+    /// Thumbnail tiles for all positions, in a near-square grid filling `area`
+    fn overview_tiles(&self, area: Rect) -> Vec<Rect> {
+        let count = self.positions.len();
+        let cols = (count as f32).sqrt().ceil().max(1.0) as usize;
+        let rows = count.div_ceil(cols);
+        let tile = Vec2::new(area.width() / cols as f32, area.height() / rows as f32);
+        (0..count)
+            .map(|index| {
+                let min = area.min + Vec2::new((index % cols) as f32 * tile.x, (index / cols) as f32 * tile.y);
+                Rect::from_min_size(min, tile)
+            })
+            .collect()
+    }
 }
 
 impl eframe::App for SfenApp {
@@ -122,122 +747,155 @@ impl eframe::App for SfenApp {
             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
         }
 
+        self.handle_navigation(ctx);
+        self.handle_edit_keys(ctx);
         self.load_textures(ctx);
 
-        let frame = egui::Frame::NONE.fill(self.background_color);
+        let dark = match self.config.appearance {
+            Appearance::Light => false,
+            Appearance::Dark => true,
+            Appearance::Auto => ctx.system_theme() == Some(egui::Theme::Dark),
+        };
+        let palette = if dark { self.dark_palette } else { self.light_palette };
+
+        self.show_palette(ctx, &palette);
+
+        let frame = egui::Frame::NONE.fill(palette.background);
 
         egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
-            let available = ui.available_size();
-
-            // Check if we need hand space (shogi only, when there are pieces in hand)
-            let has_hand = !self.is_chess && (!self.hand.sente.is_empty() || !self.hand.gote.is_empty());
-            let hand_width_ratio = if has_hand { 0.12 } else { 0.0 };
-
-            // Use width for layout when hands are shown (window is wider than tall)
-            let total_size = if has_hand { available.x } else { available.x.min(available.y) };
-            let hand_width = total_size * hand_width_ratio;
-            let board_area = total_size - hand_width * 2.0;
-            let coord_space = board_area * coord_margin(self.board_size);
-            let board_pixels = board_area - coord_space * 2.0;
-            let cell_size = board_pixels / self.board_size as f32;
-
-            // Center everything: hand + coords + board + coords + hand = total_size
-            let left_margin = (available.x - total_size) / 2.0;
-
-            // Board offset: left_margin + hand_width + coord_space
-            // Reduced vertical margin when hands are shown
-            let vertical_margin = if has_hand {
-                coord_space * 1.2
-            } else {
-                (available.y - board_pixels) / 2.0
+            let area = ui.max_rect();
+            let tiles = if self.overview { self.overview_tiles(area) } else { Vec::new() };
+            let board_area = match tiles.first() {
+                Some(tile) => *tile,
+                None if self.comparison.is_some() => split_compare_area(area).0,
+                None => area,
             };
-            let offset = Pos2::new(
-                left_margin + hand_width + coord_space,
-                vertical_margin,
-            );
+
+            // Rasterize SVG pieces at the physical cell size for crisp rendering
+            let cell_size = self.cell_size(board_area);
+            let raster_size = (cell_size * ctx.pixels_per_point()).round() as u32;
+            self.rasterize_svg_pieces(ctx, raster_size);
 
             let painter = ui.painter();
 
-            if self.is_chess {
-                draw_checkerboard(
+            if let Some(texture) = &self.background_texture {
+                draw_background_image(
                     painter,
-                    offset,
-                    cell_size,
-                    self.board_size,
-                    self.light_square_color,
-                    self.dark_square_color,
-                );
-            } else {
-                draw_grid(
-                    painter,
-                    offset,
-                    board_pixels,
-                    cell_size,
-                    self.board_size,
-                    self.grid_color,
+                    area,
+                    texture,
+                    self.config.shogi.background_image_mode,
+                    ctx.pixels_per_point(),
                 );
-                draw_hoshi_points(painter, offset, cell_size, self.board_size, self.grid_color);
             }
 
-            draw_coordinates(
-                painter,
-                offset,
-                board_pixels,
-                cell_size,
-                self.board_size,
-                self.text_color,
-            );
-            draw_pieces(
-                painter,
-                offset,
-                cell_size,
-                &self.board,
-                &self.textures,
-                self.text_color,
-                self.board_size,
-            );
+            let font = egui::FontId::proportional(cell_size * 0.3);
+            let label_inset = Vec2::splat(cell_size * 0.1);
 
-            // Draw pieces in hand (shogi only)
-            if has_hand {
-                let hand_cell_size = cell_size * 0.9;
-
-                // Gote's hand - upper left (within left hand_width area)
-                if !self.hand.gote.is_empty() {
-                    let gote_hand_pos = Pos2::new(
-                        left_margin + (hand_width - hand_cell_size) / 2.0,
-                        offset.y,
-                    );
-                    draw_hand(
-                        painter,
-                        gote_hand_pos,
-                        hand_cell_size,
-                        &self.hand.gote,
-                        &self.textures,
-                        self.text_color,
-                        self.grid_color,
-                        true,
-                    );
+            if self.overview {
+                let clicked = ui.interact(area, ui.id().with("overview"), egui::Sense::click());
+                for (index, (tile, position)) in tiles.iter().zip(&self.positions).enumerate() {
+                    self.draw_position(painter, tile.shrink(cell_size * 0.2), position, &palette, None);
+                    let label = (index + 1).to_string();
+                    painter.text(tile.min + label_inset, egui::Align2::LEFT_TOP, label, font.clone(), palette.text);
+                    if index == self.current {
+                        let stroke = Stroke::new(2.0, palette.text);
+                        painter.rect_stroke(tile.shrink(2.0), 4.0, stroke, egui::StrokeKind::Inside);
+                    }
                 }
 
-                // Sente's hand - lower right (within right hand_width area)
-                if !self.hand.sente.is_empty() {
-                    let sente_hand_count = self.hand.sente.len();
-                    let sente_hand_pos = Pos2::new(
-                        left_margin + hand_width + coord_space + board_pixels + coord_space + (hand_width - hand_cell_size) / 2.0,
-                        offset.y + board_pixels - (sente_hand_count as f32 * hand_cell_size),
-                    );
-                    draw_hand(
-                        painter,
-                        sente_hand_pos,
-                        hand_cell_size,
-                        &self.hand.sente,
-                        &self.textures,
-                        self.text_color,
-                        self.grid_color,
-                        false,
-                    );
+                // Clicking a thumbnail opens that position
+                let pointer = clicked.interact_pointer_pos().filter(|_| clicked.clicked());
+                if let Some(index) = pointer.and_then(|pos| tiles.iter().position(|tile| tile.contains(pos))) {
+                    self.current = index;
+                    self.overview = false;
                 }
+                return;
+            }
+
+            if let Some(differences) = &self.comparison {
+                let (first, second, summary) = split_compare_area(area);
+                for (index, half) in [first, second].into_iter().enumerate() {
+                    self.draw_position(painter, half, &self.positions[index], &palette, Some(differences));
+                    let label = (index + 1).to_string();
+                    painter.text(half.min + label_inset, egui::Align2::LEFT_TOP, label, font.clone(), palette.text);
+                }
+                draw_summary(painter, summary.shrink(cell_size * 0.3), &differences.summary, font, palette.text);
+                return;
+            }
+
+            if self.editor.is_some() {
+                let response = ui.interact(area, ui.id().with("editor"), egui::Sense::click_and_drag());
+                self.handle_edit_pointer(ctx, &response, &self.board_layout(area));
+            }
+
+            let position = &self.positions[self.current];
+            self.draw_position(painter, area, position, &palette, None);
+
+            // The dragged piece follows the pointer
+            let dragged = self.editor.as_ref().and_then(|editor| editor.dragged.clone()).map(|(key, _)| key);
+            if let (Some(key), Some(pointer)) = (dragged, ctx.input(|i| i.pointer.latest_pos())) {
+                let cell_size = self.cell_size(area);
+                let piece = [vec![Some(Piece { sfen_key: key })]];
+                let origin = pointer - Vec2::splat(cell_size / 2.0);
+                draw_pieces(painter, origin, cell_size, &piece, &self.textures, palette.pieces, self.variant);
+            }
+
+            if let Some(status) = &position.status {
+                painter.text(area.min + label_inset, egui::Align2::LEFT_TOP, status, font.clone(), palette.text);
+            }
+            if self.positions.len() > 1 {
+                let label = format!("{} / {}", self.current + 1, self.positions.len());
+                let corner = area.right_top() + Vec2::new(-label_inset.x, label_inset.y);
+                painter.text(corner, egui::Align2::RIGHT_TOP, label, font, palette.text);
             }
         });
     }
 }
+
+/// Hand piece size and the top-left corners of gote's hand (upper left) and sente's hand
+/// (lower right, growing upward)
+fn hand_origins(layout: &BoardLayout, hand: &Hand) -> (f32, Pos2, Pos2) {
+    // Capped so the large cells of small boards still fit the hand area
+    let cell_size = (layout.cell_size * 0.9).min(layout.hand_width * 0.92);
+    let inset = (layout.hand_width - cell_size) / 2.0;
+    let gote = Pos2::new(layout.left_hand_x() + inset, layout.offset.y);
+    let sente = Pos2::new(
+        layout.right_hand_x() + inset,
+        layout.offset.y + layout.board_size.y - hand.sente.len() as f32 * cell_size,
+    );
+    (cell_size, gote, sente)
+}
+
+/// Mark hand pieces whose counts differ, laid out as in `draw_hand`
+fn highlight_hand(
+    painter: &egui::Painter,
+    top_left: Pos2,
+    cell_size: f32,
+    hand: &[(String, u32)],
+    differences: Option<&Differences>,
+) {
+    let Some(differences) = differences else {
+        return;
+    };
+    for (idx, (sfen_key, _)) in hand.iter().enumerate() {
+        if differences.hand_keys.contains(sfen_key) {
+            let min = top_left + Vec2::new(0.0, idx as f32 * cell_size);
+            painter.rect_filled(Rect::from_min_size(min, Vec2::splat(cell_size)), 2.0, DIFFERENCE_HIGHLIGHT);
+        }
+    }
+}
+
+/// Draw the difference summary, one line per difference, as many as fit in `area`
+fn draw_summary(painter: &egui::Painter, area: Rect, lines: &[String], font: egui::FontId, color: Color32) {
+    let line_height = font.size * 1.3;
+    let fitting = ((area.height() / line_height).floor() as usize).max(1);
+    let text = if lines.is_empty() {
+        "Positions are identical".to_string()
+    } else if lines.len() <= fitting {
+        lines.join("\n")
+    } else {
+        let shown = &lines[..fitting - 1];
+        format!("{}\n… and {} more", shown.join("\n"), lines.len() - shown.len())
+    };
+    painter.text(area.min, egui::Align2::LEFT_TOP, text, font, color);
+}
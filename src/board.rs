@@ -1,46 +1,140 @@
 //! Board rendering
 
-use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Stroke, Vec2};
+use eframe::egui::{self, Align2, Color32, FontFamily, FontId, Pos2, Rect, Stroke, TextureHandle, TextureId, Vec2};
 
-use crate::sfen::{CHESS_SIZE, STANDARD_SHOGI_SIZE};
+use crate::config::{CoordinateStyle, ImageMode};
+use crate::variant::Notation;
 
-const COORD_MARGIN_STANDARD: f32 = 0.05;
-const COORD_MARGIN_OTHER: f32 = 0.10;
 const TEXT_OFFSET: f32 = 0.08;
+const FULL_UV: Rect = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
 
 const KANJI_NUMERALS: [&str; 9] = ["一", "二", "三", "四", "五", "六", "七", "八", "九"];
-const CHESS_FILES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
 
-/// Get coordinate margin based on board size
-pub fn coord_margin(board_size: usize) -> f32 {
-    if board_size == STANDARD_SHOGI_SIZE {
-        COORD_MARGIN_STANDARD
-    } else {
-        COORD_MARGIN_OTHER
+/// Kanji numeral for 1..=99 (e.g. 十二 for 12)
+fn kanji_numeral(n: usize) -> String {
+    let (tens, units) = (n / 10, n % 10);
+    let mut numeral = String::new();
+    if tens > 1 {
+        numeral.push_str(KANJI_NUMERALS[tens - 1]);
+    }
+    if tens > 0 {
+        numeral.push('十');
+    }
+    if units > 0 {
+        numeral.push_str(KANJI_NUMERALS[units - 1]);
     }
+    numeral
+}
+
+/// Fill for a set of squares: a flat color, optionally covered by an image
+#[derive(Clone, Copy)]
+pub struct SquareFill {
+    pub color: Color32,
+    pub texture: Option<TextureId>,
 }
 
-/// Draw the board grid lines (for shogi)
+/// Draw the board grid lines (for shogi), with a border of `border_width` around the outside
 pub fn draw_grid(
     painter: &egui::Painter,
     offset: Pos2,
-    board_pixels: f32,
     cell_size: f32,
-    board_size: usize,
+    (files, ranks): (usize, usize),
     color: Color32,
+    border_width: f32,
 ) {
-    for i in 0..=board_size {
-        let x = offset.x + i as f32 * cell_size;
-        let y = offset.y + i as f32 * cell_size;
+    let board_size = Vec2::new(files as f32, ranks as f32) * cell_size;
+    for col in 1..files {
+        let x = offset.x + col as f32 * cell_size;
         painter.line_segment(
-            [Pos2::new(x, offset.y), Pos2::new(x, offset.y + board_pixels)],
+            [Pos2::new(x, offset.y), Pos2::new(x, offset.y + board_size.y)],
             Stroke::new(1.0, color),
         );
+    }
+    for row in 1..ranks {
+        let y = offset.y + row as f32 * cell_size;
         painter.line_segment(
-            [Pos2::new(offset.x, y), Pos2::new(offset.x + board_pixels, y)],
+            [Pos2::new(offset.x, y), Pos2::new(offset.x + board_size.x, y)],
             Stroke::new(1.0, color),
         );
     }
+
+    let border = Rect::from_min_size(offset, board_size);
+    painter.rect_stroke(border, 0.0, Stroke::new(border_width, color), egui::StrokeKind::Middle);
+}
+
+/// Draw lines through the square centers so pieces sit on the intersections (for xiangqi)
+///
+/// Inner file lines stop at the river when there is one, and each palace (given by
+/// its top-left intersection) gets its two diagonals.
+pub fn draw_intersections(
+    painter: &egui::Painter,
+    offset: Pos2,
+    cell_size: f32,
+    (files, ranks): (usize, usize),
+    color: Color32,
+    river: bool,
+    palaces: &[(usize, usize)],
+) {
+    let point = |row: usize, col: usize| {
+        Pos2::new(
+            offset.x + (col as f32 + 0.5) * cell_size,
+            offset.y + (row as f32 + 0.5) * cell_size,
+        )
+    };
+    let stroke = Stroke::new(1.0, color);
+    let (last_col, last_row) = (files.saturating_sub(1), ranks.saturating_sub(1));
+
+    for row in 0..ranks {
+        painter.line_segment([point(row, 0), point(row, last_col)], stroke);
+    }
+    let river_row = ranks / 2;
+    for col in 0..files {
+        if river && col != 0 && col != last_col {
+            painter.line_segment([point(0, col), point(river_row - 1, col)], stroke);
+            painter.line_segment([point(river_row, col), point(last_row, col)], stroke);
+        } else {
+            painter.line_segment([point(0, col), point(last_row, col)], stroke);
+        }
+    }
+
+    for &(row, col) in palaces {
+        painter.line_segment([point(row, col), point(row + 2, col + 2)], stroke);
+        painter.line_segment([point(row, col + 2), point(row + 2, col)], stroke);
+    }
+
+    let frame = Rect::from_min_max(point(0, 0), point(last_row, last_col)).expand(cell_size * 0.12);
+    painter.rect_stroke(frame, 0.0, Stroke::new(2.0, color), egui::StrokeKind::Middle);
+
+    if river {
+        let font = FontId::new(cell_size * 0.45, FontFamily::Name("YujiMai".into()));
+        let y = offset.y + river_row as f32 * cell_size;
+        let quarter = files as f32 * cell_size / 4.0;
+        painter.text(Pos2::new(offset.x + quarter, y), Align2::CENTER_CENTER, "楚 河", font.clone(), color);
+        painter.text(Pos2::new(offset.x + quarter * 3.0, y), Align2::CENTER_CENTER, "漢 界", font, color);
+    }
+}
+
+/// Shade each side's promotion zone (the `zone_ranks` ranks nearest each edge)
+pub fn draw_promotion_zones(
+    painter: &egui::Painter,
+    offset: Pos2,
+    cell_size: f32,
+    (files, ranks): (usize, usize),
+    zone_ranks: usize,
+    color: Color32,
+) {
+    if zone_ranks == 0 {
+        return;
+    }
+
+    let zone_size = Vec2::new(files as f32, zone_ranks as f32) * cell_size;
+    let top = Rect::from_min_size(offset, zone_size);
+    let bottom = Rect::from_min_size(
+        Pos2::new(offset.x, offset.y + (ranks - zone_ranks) as f32 * cell_size),
+        zone_size,
+    );
+    painter.rect_filled(top, 0.0, color);
+    painter.rect_filled(bottom, 0.0, color);
 }
 
 /// Draw a checkerboard pattern (for chess)
@@ -48,97 +142,202 @@ pub fn draw_checkerboard(
     painter: &egui::Painter,
     offset: Pos2,
     cell_size: f32,
-    board_size: usize,
-    light_color: Color32,
-    dark_color: Color32,
+    (files, ranks): (usize, usize),
+    light: SquareFill,
+    dark: SquareFill,
 ) {
-    for row in 0..board_size {
-        for col in 0..board_size {
-            let is_light = (row + col) % 2 == 0;
-            let color = if is_light { light_color } else { dark_color };
+    for row in 0..ranks {
+        for col in 0..files {
+            // The bottom-left square is always dark, as in chess (a1)
+            let is_light = (ranks - 1 - row + col) % 2 == 1;
+            let fill = if is_light { light } else { dark };
             let rect = Rect::from_min_size(
                 Pos2::new(offset.x + col as f32 * cell_size, offset.y + row as f32 * cell_size),
                 Vec2::splat(cell_size),
             );
-            painter.rect_filled(rect, 0.0, color);
+            if let Some(texture) = fill.texture {
+                painter.image(texture, rect, FULL_UV, Color32::WHITE);
+            } else {
+                painter.rect_filled(rect, 0.0, fill.color);
+            }
         }
     }
 }
 
+/// Draw a background image over `rect`, stretched or tiled at its natural size
+pub fn draw_background_image(
+    painter: &egui::Painter,
+    rect: Rect,
+    texture: &TextureHandle,
+    mode: ImageMode,
+    pixels_per_point: f32,
+) {
+    let uv = match mode {
+        ImageMode::Stretch => FULL_UV,
+        ImageMode::Tile => {
+            let tile_size = texture.size_vec2() / pixels_per_point;
+            Rect::from_min_max(Pos2::ZERO, (rect.size() / tile_size).to_pos2())
+        }
+    };
+    painter.image(texture.id(), rect, uv, Color32::WHITE);
+}
+
+/// How coordinates are labeled and where they are drawn
+#[derive(Clone, Copy)]
+pub struct CoordinateOptions {
+    pub style: CoordinateStyle,
+    pub notation: Notation,
+    pub all_sides: bool,
+}
+
+/// Letter label for a zero-based index ('a', 'b', ...)
+fn letter(index: usize) -> String {
+    char::from(b'a' + index as u8).to_string()
+}
+
+/// Coordinate labels as (files left to right, ranks top to bottom)
+fn coordinate_labels(
+    style: CoordinateStyle,
+    notation: Notation,
+    (files, ranks): (usize, usize),
+) -> (Vec<String>, Vec<String>) {
+    let is_chess_board = notation == Notation::Chess;
+    match style {
+        CoordinateStyle::Hidden => (Vec::new(), Vec::new()),
+        // Red's files are counted in Chinese numerals from red's right; no rank labels
+        CoordinateStyle::Japanese if notation == Notation::Xiangqi => {
+            ((1..=files).rev().map(kanji_numeral).collect(), Vec::new())
+        }
+        // ICCS: files a-i, ranks 0-9 from red's side
+        CoordinateStyle::Western if notation == Notation::Xiangqi => (
+            (0..files).map(letter).collect(),
+            (0..ranks).rev().map(|rank| rank.to_string()).collect(),
+        ),
+        // Janggi: files 1-9 from the left, ranks 1-9 then 0 from the top
+        CoordinateStyle::Japanese if notation == Notation::Janggi => (
+            (1..=files).map(|file| file.to_string()).collect(),
+            (1..=ranks).map(|rank| (rank % 10).to_string()).collect(),
+        ),
+        CoordinateStyle::Western if notation == Notation::Janggi => (
+            (0..files).map(letter).collect(),
+            (1..=ranks).rev().map(|rank| rank.to_string()).collect(),
+        ),
+        CoordinateStyle::Numeric if notation == Notation::Xiangqi || notation == Notation::Janggi => (
+            (1..=files).map(|file| file.to_string()).collect(),
+            (1..=ranks).rev().map(|rank| rank.to_string()).collect(),
+        ),
+        // Chess has no Japanese notation, so it shares the western labels
+        CoordinateStyle::Japanese | CoordinateStyle::Western if is_chess_board => (
+            (0..files).map(letter).collect(),
+            (1..=ranks).rev().map(|rank| rank.to_string()).collect(),
+        ),
+        CoordinateStyle::Numeric if is_chess_board => (
+            (1..=files).map(|file| file.to_string()).collect(),
+            (1..=ranks).rev().map(|rank| rank.to_string()).collect(),
+        ),
+        // Shogi files are numbered right to left in every style
+        CoordinateStyle::Japanese => (
+            (1..=files).rev().map(|file| file.to_string()).collect(),
+            (1..=ranks).map(kanji_numeral).collect(),
+        ),
+        CoordinateStyle::Western => (
+            (1..=files).rev().map(|file| file.to_string()).collect(),
+            (0..ranks).map(letter).collect(),
+        ),
+        CoordinateStyle::Numeric => (
+            (1..=files).rev().map(|file| file.to_string()).collect(),
+            (1..=ranks).map(|rank| rank.to_string()).collect(),
+        ),
+    }
+}
+
+/// Name of the (row, col) square in western notation, e.g. "7g" in shogi or "e4" in chess
+pub fn square_name(notation: Notation, dimensions: (usize, usize), (row, col): (usize, usize)) -> String {
+    let (files, ranks) = coordinate_labels(CoordinateStyle::Western, notation, dimensions);
+    format!("{}{}", files[col], ranks[row])
+}
+
 /// Draw board coordinates
 pub fn draw_coordinates(
     painter: &egui::Painter,
     offset: Pos2,
-    board_pixels: f32,
     cell_size: f32,
-    board_size: usize,
+    dimensions: (usize, usize),
     color: Color32,
+    options: CoordinateOptions,
 ) {
     let font_size = cell_size * 0.35;
     let font = FontId::proportional(font_size);
     let margin = cell_size * TEXT_OFFSET;
 
-    if board_size == CHESS_SIZE {
-        // Chess: files a-h (left to right), ranks 8-1 (top to bottom)
-        for (col, file) in CHESS_FILES.iter().enumerate() {
+    let (files, ranks) = coordinate_labels(options.style, options.notation, dimensions);
+
+    // Chess, xiangqi and janggi label files below and ranks on the left; shogi above and on the right
+    let is_chess_board = options.notation != Notation::Shogi;
+    let top = offset.y - margin;
+    let bottom = offset.y + dimensions.1 as f32 * cell_size + margin;
+    let left = offset.x - margin;
+    let right = offset.x + dimensions.0 as f32 * cell_size + margin;
+    let (file_sides, rank_sides) = if is_chess_board {
+        (
+            [(bottom, Align2::CENTER_TOP), (top, Align2::CENTER_BOTTOM)],
+            [(left, Align2::RIGHT_CENTER), (right, Align2::LEFT_CENTER)],
+        )
+    } else {
+        (
+            [(top, Align2::CENTER_BOTTOM), (bottom, Align2::CENTER_TOP)],
+            [(right, Align2::LEFT_CENTER), (left, Align2::RIGHT_CENTER)],
+        )
+    };
+    let side_count = if options.all_sides { 2 } else { 1 };
+    let traditional_xiangqi = options.notation == Notation::Xiangqi && options.style == CoordinateStyle::Japanese;
+    let file_side_count = if traditional_xiangqi { 1 } else { side_count };
+
+    for &(y, align) in file_sides.iter().take(file_side_count) {
+        for (col, label) in files.iter().enumerate() {
             let x = offset.x + (col as f32 + 0.5) * cell_size;
-            let y = offset.y + board_pixels + margin;
-            painter.text(
-                Pos2::new(x, y),
-                Align2::CENTER_TOP,
-                *file,
-                font.clone(),
-                color,
-            );
+            painter.text(Pos2::new(x, y), align, label, font.clone(), color);
         }
-        for row in 0..board_size {
-            let rank = board_size - row;
-            let x = offset.x - margin;
-            let y = offset.y + (row as f32 + 0.5) * cell_size;
-            painter.text(
-                Pos2::new(x, y),
-                Align2::RIGHT_CENTER,
-                rank.to_string(),
-                font.clone(),
-                color,
-            );
-        }
-    } else {
-        // Shogi: files numbered right to left, ranks as kanji
-        for col in 0..board_size {
-            let file_num = board_size - col;
+    }
+    // Traditional xiangqi diagrams always number black's files too, from black's right
+    if traditional_xiangqi {
+        let (y, align) = file_sides[1];
+        for col in 0..dimensions.0 {
             let x = offset.x + (col as f32 + 0.5) * cell_size;
-            let y = offset.y - margin;
-            painter.text(
-                Pos2::new(x, y),
-                Align2::CENTER_BOTTOM,
-                file_num.to_string(),
-                font.clone(),
-                color,
-            );
+            painter.text(Pos2::new(x, y), align, (col + 1).to_string(), font.clone(), color);
         }
-        for (row, kanji) in KANJI_NUMERALS.iter().enumerate().take(board_size) {
-            let x = offset.x + board_pixels + margin;
+    }
+    for &(x, align) in rank_sides.iter().take(side_count) {
+        for (row, label) in ranks.iter().enumerate() {
             let y = offset.y + (row as f32 + 0.5) * cell_size;
-            painter.text(
-                Pos2::new(x, y),
-                Align2::LEFT_CENTER,
-                *kanji,
-                font.clone(),
-                color,
-            );
+            painter.text(Pos2::new(x, y), align, label, font.clone(), color);
         }
     }
 }
 
-/// Draw hoshi (star) points on standard shogi board
-pub fn draw_hoshi_points(painter: &egui::Painter, offset: Pos2, cell_size: f32, board_size: usize, color: Color32) {
-    if board_size != STANDARD_SHOGI_SIZE {
-        return;
+/// Mark the given (row, col) squares with a small triangle in their top-right corner
+pub fn draw_castling_markers(
+    painter: &egui::Painter,
+    offset: Pos2,
+    cell_size: f32,
+    squares: &[(usize, usize)],
+    color: Color32,
+) {
+    let size = cell_size * 0.18;
+    for &(row, col) in squares {
+        let corner = Pos2::new(
+            offset.x + (col + 1) as f32 * cell_size,
+            offset.y + row as f32 * cell_size,
+        );
+        let points = vec![corner, corner - Vec2::new(size, 0.0), corner + Vec2::new(0.0, size)];
+        painter.add(egui::Shape::convex_polygon(points, color, Stroke::NONE));
     }
+}
 
+/// Draw hoshi (star) points at the given (row, col) grid intersections
+pub fn draw_hoshi_points(painter: &egui::Painter, offset: Pos2, cell_size: f32, hoshi: &[(usize, usize)], color: Color32) {
     let hoshi_radius = cell_size * 0.06;
-    for (row, col) in [(3, 3), (3, 6), (6, 3), (6, 6)] {
+    for &(row, col) in hoshi {
         let center = Pos2::new(
             offset.x + col as f32 * cell_size,
             offset.y + row as f32 * cell_size,
@@ -0,0 +1,53 @@
+//! Command-line argument parsing
+
+use std::path::PathBuf;
+
+/// Usage line shown on argument errors
+pub const USAGE: &str = "usage: sfen-bro [--variant <name>] [--start-position] [--compare] [--position <sfen>]... [path-to-config.json]";
+
+/// Parsed command-line arguments
+#[derive(Default)]
+pub struct Args {
+    /// Path to the JSON config file
+    pub config_path: Option<PathBuf>,
+    /// Variant identifier that overrides detection
+    pub variant: Option<String>,
+    /// Show the start position when the clipboard holds no position
+    pub start_position: bool,
+    /// Show the first two positions side by side with their differences
+    pub compare: bool,
+    /// Positions given on the command line, used instead of the clipboard
+    pub positions: Vec<String>,
+}
+
+/// Parse command-line arguments (without the program name)
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--variant" {
+            let value = args.next().ok_or("--variant requires a value")?;
+            parsed.variant = Some(value);
+        } else if let Some(value) = arg.strip_prefix("--variant=") {
+            parsed.variant = Some(value.to_string());
+        } else if arg == "--start-position" {
+            parsed.start_position = true;
+        } else if arg == "--compare" {
+            parsed.compare = true;
+        } else if arg == "--position" {
+            let value = args.next().ok_or("--position requires a value")?;
+            parsed.positions.push(value);
+        } else if let Some(value) = arg.strip_prefix("--position=") {
+            parsed.positions.push(value.to_string());
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option '{}'", arg));
+        } else if parsed.config_path.is_none() {
+            parsed.config_path = Some(PathBuf::from(arg));
+        } else {
+            return Err(format!("unexpected argument '{}'", arg));
+        }
+    }
+
+    Ok(parsed)
+}
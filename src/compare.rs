@@ -0,0 +1,141 @@
+//! Differences between two positions of the same variant
+
+use std::collections::BTreeMap;
+
+use crate::board::square_name;
+use crate::sfen::{parse_sfen, Hand};
+use crate::variant::{parse_position_hand, Family, SideColors, Variant};
+
+/// What differs between two positions
+pub struct Differences {
+    /// Squares whose contents differ, as (row, col)
+    pub squares: Vec<(usize, usize)>,
+    /// Hand pieces whose counts differ (sente's keys uppercase, gote's lowercase)
+    pub hand_keys: Vec<String>,
+    /// One line per difference, for display
+    pub summary: Vec<String>,
+}
+
+/// Compare two positions square by square, then hand counts and side to move
+pub fn compare_positions(variant: &dyn Variant, first: &str, second: &str) -> Differences {
+    let (files, ranks) = variant.dimensions();
+    let board = |sfen: &str| -> Vec<Vec<Option<String>>> {
+        let board = parse_sfen(sfen, files, ranks).unwrap_or_else(|_| vec![vec![None; files]; ranks]);
+        board
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|piece| piece.map(|piece| variant.normalize_key(&piece.sfen_key)))
+                    .collect()
+            })
+            .collect()
+    };
+    let (first_board, second_board) = (board(first), board(second));
+
+    let mut squares = Vec::new();
+    let mut summary = Vec::new();
+    for (row, (first_row, second_row)) in first_board.iter().zip(&second_board).enumerate() {
+        for (col, (a, b)) in first_row.iter().zip(second_row).enumerate() {
+            if a != b {
+                squares.push((row, col));
+                let name = square_name(variant.notation(), (files, ranks), (row, col));
+                summary.push(format!("{}: {} → {}", name, describe(a), describe(b)));
+            }
+        }
+    }
+
+    let first_counts = hand_counts(&parse_position_hand(variant, first));
+    let second_counts = hand_counts(&parse_position_hand(variant, second));
+    let (sente, gote) = side_names(variant);
+    let mut hand_keys = Vec::new();
+    for key in first_counts.keys().chain(second_counts.keys()) {
+        let (a, b) = (first_counts.get(key).unwrap_or(&0), second_counts.get(key).unwrap_or(&0));
+        if a != b && !hand_keys.contains(key) {
+            hand_keys.push(key.clone());
+            let side = if key.chars().any(char::is_uppercase) { sente } else { gote };
+            summary.push(format!("{} hand {}: {} → {}", side, key.to_uppercase(), a, b));
+        }
+    }
+
+    let side_to_move = |sfen: &str| sfen.split_whitespace().nth(1).unwrap_or("-").to_string();
+    let (first_side, second_side) = (side_to_move(first), side_to_move(second));
+    if first_side != second_side {
+        summary.push(format!("Side to move: {} → {}", first_side, second_side));
+    }
+
+    Differences {
+        squares,
+        hand_keys,
+        summary,
+    }
+}
+
+/// Piece key, or "empty" for an empty square
+fn describe(piece: &Option<String>) -> &str {
+    piece.as_deref().unwrap_or("empty")
+}
+
+/// Counts of both hands by key (keys of the two sides differ in case)
+fn hand_counts(hand: &Hand) -> BTreeMap<String, u32> {
+    hand.sente.iter().chain(&hand.gote).cloned().collect()
+}
+
+/// Names of the uppercase and lowercase sides
+fn side_names(variant: &dyn Variant) -> (&'static str, &'static str) {
+    match variant.family() {
+        Family::Shogi => ("Sente", "Gote"),
+        Family::Chess => ("White", "Black"),
+        Family::Xiangqi => match variant.side_colors() {
+            SideColors::RedBlack => ("Red", "Black"),
+            SideColors::BlueRed => ("Blue", "Red"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::{CHESS, STANDARD_SHOGI};
+
+    #[test]
+    fn capture_moves_a_piece_and_fills_the_hand() {
+        let before = "4k4/9/4p4/9/9/9/9/4R4/4K4 b - 1";
+        let after = "4k4/9/4R4/9/9/9/9/9/4K4 w P 2";
+        let differences = compare_positions(&STANDARD_SHOGI, before, after);
+
+        assert_eq!(differences.squares, [(2, 4), (7, 4)]);
+        assert_eq!(differences.hand_keys, ["P"]);
+        assert_eq!(
+            differences.summary,
+            ["5c: p → R", "5h: R → empty", "Sente hand P: 0 → 1", "Side to move: b → w"]
+        );
+    }
+
+    #[test]
+    fn drop_adds_a_square_and_empties_the_hand() {
+        let before = "4k4/9/9/9/9/9/9/9/4K4 w Pp 2";
+        let after = "4k4/9/9/9/4p4/9/9/9/4K4 b P 3";
+        let differences = compare_positions(&STANDARD_SHOGI, before, after);
+
+        assert_eq!(differences.squares, [(4, 4)]);
+        assert_eq!(differences.hand_keys, ["p"]);
+        assert_eq!(differences.summary, ["5e: empty → p", "Gote hand P: 1 → 0", "Side to move: w → b"]);
+    }
+
+    #[test]
+    fn chess_move_uses_chess_square_names() {
+        let before = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let after = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        let differences = compare_positions(&CHESS, before, after);
+
+        assert_eq!(differences.summary, ["e4: empty → P", "e2: P → empty", "Side to move: w → b"]);
+        assert!(differences.hand_keys.is_empty());
+    }
+
+    #[test]
+    fn identical_positions_have_no_differences() {
+        let start = STANDARD_SHOGI.start_position();
+        let differences = compare_positions(&STANDARD_SHOGI, start, start);
+        assert!(differences.squares.is_empty() && differences.summary.is_empty());
+    }
+}
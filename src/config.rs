@@ -4,7 +4,39 @@ use eframe::egui::Color32;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::themes::builtin_theme;
+use crate::variant::Notation;
+
+/// A piece set packed into a single image, sliced into a grid of equal cells
+#[derive(Deserialize)]
+pub struct SpriteSheetConfig {
+    /// Path to the sheet image (relative to assets_path, or absolute)
+    pub path: String,
+
+    /// Number of cell rows in the sheet (default: number of layout rows)
+    #[serde(default)]
+    pub rows: Option<usize>,
+
+    /// Number of cell columns in the sheet (default: longest layout row)
+    #[serde(default)]
+    pub cols: Option<usize>,
+
+    /// SFEN/FEN key for each cell, row by row (empty string for unused cells)
+    pub layout: Vec<Vec<String>>,
+}
+
+/// How a background image covers its area
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageMode {
+    /// Scale the image to fill the area
+    #[default]
+    Stretch,
+    /// Repeat the image at its natural size
+    Tile,
+}
 
 /// Shogi-specific configuration
 #[derive(Deserialize, Default)]
@@ -13,6 +45,14 @@ pub struct ShogiConfig {
     #[serde(default)]
     pub background: Option<String>,
 
+    /// Background image behind the grid (relative to assets_path, or absolute)
+    #[serde(default)]
+    pub background_image: Option<String>,
+
+    /// Whether the background image is stretched or tiled (default: stretch)
+    #[serde(default)]
+    pub background_image_mode: ImageMode,
+
     /// Grid line color in HTML notation
     #[serde(default)]
     pub grid_color: Option<String>,
@@ -21,13 +61,37 @@ pub struct ShogiConfig {
     #[serde(default)]
     pub text_color: Option<String>,
 
+    /// Draw hoshi (star) points where the variant has them (default: true)
+    #[serde(default)]
+    pub hoshi: Option<bool>,
+
+    /// Shade each side's promotion zone (default: false)
+    #[serde(default)]
+    pub promotion_zones: Option<bool>,
+
+    /// Promotion zone shading color in HTML notation (default: translucent black)
+    #[serde(default)]
+    pub promotion_zone_color: Option<String>,
+
+    /// Outer border line width in points (default: 1.0, same as the grid lines)
+    #[serde(default)]
+    pub border_width: Option<f32>,
+
     /// Base path for piece images (relative to config file or absolute)
     #[serde(default)]
     pub assets_path: Option<String>,
 
+    /// Sprite sheet containing a whole piece set (overridden per piece by `pieces`)
+    #[serde(default)]
+    pub sprite_sheet: Option<SpriteSheetConfig>,
+
     /// Piece texture mappings (SFEN key -> path relative to assets_path, or absolute)
     #[serde(default)]
     pub pieces: HashMap<String, String>,
+
+    /// Derive missing sente/gote images by rotating the other side's (default: true)
+    #[serde(default)]
+    pub auto_rotate: Option<bool>,
 }
 
 /// Chess-specific configuration
@@ -41,9 +105,122 @@ pub struct ChessConfig {
     #[serde(default)]
     pub dark_squares: Option<String>,
 
+    /// Image drawn on each light square (relative to assets_path, or absolute)
+    #[serde(default)]
+    pub light_square_image: Option<String>,
+
+    /// Image drawn on each dark square (relative to assets_path, or absolute)
+    #[serde(default)]
+    pub dark_square_image: Option<String>,
+
     /// Text color for coordinates and pieces in HTML notation
     #[serde(default)]
     pub text_color: Option<String>,
+
+    /// Base path for piece images (relative to config file or absolute)
+    #[serde(default)]
+    pub assets_path: Option<String>,
+
+    /// Sprite sheet containing a whole piece set (overridden per piece by `pieces`)
+    #[serde(default)]
+    pub sprite_sheet: Option<SpriteSheetConfig>,
+
+    /// Piece texture mappings (FEN key -> path relative to assets_path, or absolute)
+    #[serde(default)]
+    pub pieces: HashMap<String, String>,
+
+    /// Mark rooks that keep castling rights (default: false)
+    #[serde(default)]
+    pub castling_markers: Option<bool>,
+
+    /// Outer border line width in points for grid boards such as makruk (default: 1.0)
+    #[serde(default)]
+    pub border_width: Option<f32>,
+}
+
+/// Xiangqi and janggi configuration
+#[derive(Deserialize, Default)]
+pub struct XiangqiConfig {
+    /// Board background color in HTML notation
+    #[serde(default)]
+    pub background: Option<String>,
+
+    /// Line color (grid, river and palaces) in HTML notation
+    #[serde(default)]
+    pub grid_color: Option<String>,
+
+    /// Text color for coordinates in HTML notation
+    #[serde(default)]
+    pub text_color: Option<String>,
+
+    /// Fill color of the piece discs in HTML notation
+    #[serde(default)]
+    pub piece_background: Option<String>,
+
+    /// Red (uppercase xiangqi, lowercase janggi) piece color in HTML notation
+    #[serde(default)]
+    pub red_color: Option<String>,
+
+    /// Black (lowercase xiangqi) piece color in HTML notation
+    #[serde(default)]
+    pub black_color: Option<String>,
+
+    /// Blue (uppercase janggi) piece color in HTML notation
+    #[serde(default)]
+    pub blue_color: Option<String>,
+
+    /// Base path for piece images (relative to config file or absolute)
+    #[serde(default)]
+    pub assets_path: Option<String>,
+
+    /// Sprite sheet containing a whole piece set (overridden per piece by `pieces`)
+    #[serde(default)]
+    pub sprite_sheet: Option<SpriteSheetConfig>,
+
+    /// Piece texture mappings (FEN key -> path relative to assets_path, or absolute)
+    #[serde(default)]
+    pub pieces: HashMap<String, String>,
+}
+
+/// Coordinate notation style
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CoordinateStyle {
+    /// Arabic files and kanji ranks (shogi default); Chinese-numeral files for xiangqi
+    Japanese,
+    /// Arabic files and letter ranks as in USI for shogi, a-h/1-8 for chess (chess default)
+    Western,
+    /// Digits for both files and ranks
+    Numeric,
+    /// No coordinates
+    #[serde(rename = "none")]
+    Hidden,
+}
+
+/// Color scheme selection
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    /// Always use the configured colors
+    #[default]
+    Light,
+    /// Always use the dark theme
+    Dark,
+    /// Follow the desktop's dark mode preference
+    Auto,
+}
+
+/// Color theme: the same `shogi`/`chess`/`xiangqi` sections as the config, colors only
+#[derive(Deserialize, Default)]
+pub struct Theme {
+    #[serde(default)]
+    pub shogi: ShogiConfig,
+
+    #[serde(default)]
+    pub chess: ChessConfig,
+
+    #[serde(default)]
+    pub xiangqi: XiangqiConfig,
 }
 
 /// Application configuration
@@ -53,6 +230,30 @@ pub struct Config {
     #[serde(default)]
     pub scale: Option<f32>,
 
+    /// Built-in theme name or path to a theme JSON file (relative to config file or absolute)
+    #[serde(default)]
+    pub theme: Option<String>,
+
+    /// Coordinate notation style (default: japanese for shogi, western for chess)
+    #[serde(default)]
+    pub coordinates: Option<CoordinateStyle>,
+
+    /// Draw coordinates on all four sides of the board (default: false)
+    #[serde(default)]
+    pub coordinates_all_sides: bool,
+
+    /// Color scheme: light, dark, or auto (default: light)
+    #[serde(default)]
+    pub appearance: Appearance,
+
+    /// Theme used for the dark color scheme (default: the built-in "dark" theme)
+    #[serde(default)]
+    pub dark_theme: Option<String>,
+
+    /// Resolved dark color scheme
+    #[serde(skip)]
+    pub dark: Theme,
+
     /// Shogi-specific settings
     #[serde(default)]
     pub shogi: ShogiConfig,
@@ -60,6 +261,10 @@ pub struct Config {
     /// Chess-specific settings
     #[serde(default)]
     pub chess: ChessConfig,
+
+    /// Xiangqi and janggi settings
+    #[serde(default)]
+    pub xiangqi: XiangqiConfig,
 }
 
 impl ShogiConfig {
@@ -86,6 +291,42 @@ impl ShogiConfig {
             .and_then(|s| parse_html_color(s))
             .unwrap_or(Color32::BLACK)
     }
+
+    /// Parse promotion zone color from HTML notation to Color32
+    pub fn promotion_zone_color(&self) -> Color32 {
+        self.promotion_zone_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_black_alpha(24))
+    }
+
+    /// Whether to draw hoshi points (default true)
+    pub fn show_hoshi(&self) -> bool {
+        self.hoshi.unwrap_or(true)
+    }
+
+    /// Whether to shade promotion zones (default false)
+    pub fn show_promotion_zones(&self) -> bool {
+        self.promotion_zones.unwrap_or(false)
+    }
+
+    /// Get outer border width (default 1.0)
+    pub fn border_width(&self) -> f32 {
+        self.border_width.unwrap_or(1.0).max(0.0)
+    }
+
+    /// Fill colors not set explicitly from a theme
+    pub fn inherit_colors(&mut self, theme: ShogiConfig) {
+        self.background = self.background.take().or(theme.background);
+        self.grid_color = self.grid_color.take().or(theme.grid_color);
+        self.text_color = self.text_color.take().or(theme.text_color);
+        self.promotion_zone_color = self.promotion_zone_color.take().or(theme.promotion_zone_color);
+    }
+
+    /// Whether to derive missing piece images by rotation (default true)
+    pub fn auto_rotate(&self) -> bool {
+        self.auto_rotate.unwrap_or(true)
+    }
 }
 
 impl ChessConfig {
@@ -112,6 +353,103 @@ impl ChessConfig {
             .and_then(|s| parse_html_color(s))
             .unwrap_or(Color32::BLACK)
     }
+
+    /// Whether to mark rooks with castling rights (default false)
+    pub fn show_castling_markers(&self) -> bool {
+        self.castling_markers.unwrap_or(false)
+    }
+
+    /// Get outer border width of grid boards (default 1.0)
+    pub fn border_width(&self) -> f32 {
+        self.border_width.unwrap_or(1.0).max(0.0)
+    }
+
+    /// Fill colors not set explicitly from a theme
+    pub fn inherit_colors(&mut self, theme: ChessConfig) {
+        self.light_squares = self.light_squares.take().or(theme.light_squares);
+        self.dark_squares = self.dark_squares.take().or(theme.dark_squares);
+        self.text_color = self.text_color.take().or(theme.text_color);
+    }
+}
+
+impl XiangqiConfig {
+    /// Parse background color from HTML notation to Color32
+    pub fn background_color(&self) -> Color32 {
+        self.background
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(236, 204, 150))
+    }
+
+    /// Parse grid color from HTML notation to Color32
+    pub fn grid_color(&self) -> Color32 {
+        self.grid_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(60, 40, 20))
+    }
+
+    /// Parse text color from HTML notation to Color32
+    pub fn text_color(&self) -> Color32 {
+        self.text_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(60, 40, 20))
+    }
+
+    /// Parse piece disc color from HTML notation to Color32
+    pub fn piece_background_color(&self) -> Color32 {
+        self.piece_background
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(250, 236, 205))
+    }
+
+    /// Parse red piece color from HTML notation to Color32
+    pub fn red_piece_color(&self) -> Color32 {
+        self.red_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(190, 30, 30))
+    }
+
+    /// Parse black piece color from HTML notation to Color32
+    pub fn black_piece_color(&self) -> Color32 {
+        self.black_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(20, 20, 20))
+    }
+
+    /// Parse blue piece color from HTML notation to Color32
+    pub fn blue_piece_color(&self) -> Color32 {
+        self.blue_color
+            .as_ref()
+            .and_then(|s| parse_html_color(s))
+            .unwrap_or(Color32::from_rgb(30, 70, 170))
+    }
+
+    /// Fill colors not set explicitly from a theme
+    pub fn inherit_colors(&mut self, theme: XiangqiConfig) {
+        self.background = self.background.take().or(theme.background);
+        self.grid_color = self.grid_color.take().or(theme.grid_color);
+        self.text_color = self.text_color.take().or(theme.text_color);
+        self.piece_background = self.piece_background.take().or(theme.piece_background);
+        self.red_color = self.red_color.take().or(theme.red_color);
+        self.black_color = self.black_color.take().or(theme.black_color);
+        self.blue_color = self.blue_color.take().or(theme.blue_color);
+    }
+}
+
+impl SpriteSheetConfig {
+    /// Grid dimensions as (rows, cols)
+    pub fn grid(&self) -> (usize, usize) {
+        let rows = self.rows.unwrap_or(self.layout.len());
+        let cols = self
+            .cols
+            .unwrap_or_else(|| self.layout.iter().map(Vec::len).max().unwrap_or(0));
+        (rows, cols)
+    }
 }
 
 impl Config {
@@ -119,6 +457,21 @@ impl Config {
     pub fn scale_factor(&self) -> f32 {
         self.scale.unwrap_or(1.0).max(0.1)
     }
+
+    /// Get coordinate style, defaulting to the game's traditional notation
+    pub fn coordinate_style(&self, notation: Notation) -> CoordinateStyle {
+        self.coordinates.unwrap_or(match notation {
+            Notation::Shogi | Notation::Xiangqi | Notation::Janggi => CoordinateStyle::Japanese,
+            Notation::Chess => CoordinateStyle::Western,
+        })
+    }
+
+    /// Apply a theme's colors underneath the explicitly configured ones
+    pub fn apply_theme(&mut self, theme: Theme) {
+        self.shogi.inherit_colors(theme.shogi);
+        self.chess.inherit_colors(theme.chess);
+        self.xiangqi.inherit_colors(theme.xiangqi);
+    }
 }
 
 /// Parse HTML color notation (#RGB, #RRGGBB, or #RRGGBBAA)
@@ -154,8 +507,36 @@ pub fn load_config(path: &PathBuf) -> Config {
         eprintln!("Failed to read config file: {}", e);
         std::process::exit(1);
     });
-    serde_json::from_str(&content).unwrap_or_else(|e| {
+    let mut config: Config = serde_json::from_str(&content).unwrap_or_else(|e| {
         eprintln!("Failed to parse config file: {}", e);
         std::process::exit(1);
+    });
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    if let Some(name) = config.theme.clone() {
+        config.apply_theme(load_theme(&name, base_dir));
+    }
+    if config.appearance != Appearance::Light {
+        config.dark = load_theme(config.dark_theme.as_deref().unwrap_or("dark"), base_dir);
+    }
+
+    config
+}
+
+/// Load a built-in theme by name, or a theme file relative to `base_dir`
+fn load_theme(name: &str, base_dir: &Path) -> Theme {
+    let content = match builtin_theme(name) {
+        Some(json) => json.to_string(),
+        None => {
+            let theme_path = base_dir.join(name);
+            fs::read_to_string(&theme_path).unwrap_or_else(|e| {
+                eprintln!("Unknown theme '{}' and failed to read it as a file: {}", name, e);
+                std::process::exit(1);
+            })
+        }
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("Failed to parse theme '{}': {}", name, e);
+        std::process::exit(1);
     })
 }
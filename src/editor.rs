@@ -0,0 +1,275 @@
+//! Board editor: editing operations on a position and writing it back as SFEN/FEN
+
+use crate::sfen::{opposite_side_key, Hand, Piece};
+use crate::variant::{Family, Variant};
+
+/// Where a dragged piece is picked up or dropped
+#[derive(Clone, Copy, PartialEq)]
+pub enum Spot {
+    /// A board square as (row, col)
+    Square(usize, usize),
+    /// One side's hand (gote's or black's when `gote`)
+    Hand { gote: bool },
+}
+
+/// Editor state while edit mode is on
+#[derive(Default)]
+pub struct Editor {
+    /// Palette piece placed by clicking a square
+    pub brush: Option<String>,
+    /// Piece being dragged, already taken from its square or hand, and where it came from
+    pub dragged: Option<(String, Spot)>,
+    /// Feedback shown below the palette, e.g. the copied SFEN
+    pub notice: Option<String>,
+}
+
+/// Palette pieces: every piece letter of the variant, first side then second side
+pub fn palette_keys(variant: &dyn Variant) -> Vec<String> {
+    let letters = variant.piece_letters();
+    let first = letters.chars().map(|ch| ch.to_string());
+    let second = letters.chars().map(|ch| ch.to_ascii_lowercase().to_string());
+    first.chain(second).collect()
+}
+
+/// The promoted or demoted form of a piece, if the variant has one (Kyoto shogi pieces flip
+/// without a promotion zone, so only the label decides)
+pub fn toggle_promotion(variant: &dyn Variant, key: &str) -> Option<String> {
+    if let Some(base) = key.strip_prefix('+') {
+        return Some(base.to_string());
+    }
+    let promoted = format!("+{}", key);
+    (variant.piece_label(&promoted) != "?").then_some(promoted)
+}
+
+/// Put a piece into a side's hand, demoted and owned by that side
+///
+/// Kings never go to a hand, and shogi hands only take the variant's own piece letters.
+/// Returns false if the piece was refused.
+pub fn add_to_hand(variant: &dyn Variant, hand: &mut Hand, key: &str, gote: bool) -> bool {
+    let base = key.trim_start_matches('+');
+    let is_king = base.eq_ignore_ascii_case("K");
+    let is_foreign = variant.family() == Family::Shogi && !variant.piece_letters().contains(&base.to_uppercase());
+    if is_king || is_foreign {
+        return false;
+    }
+
+    let (pieces, key) = if gote {
+        (&mut hand.gote, base.to_lowercase())
+    } else {
+        (&mut hand.sente, base.to_uppercase())
+    };
+    match pieces.iter_mut().find(|(piece, _)| *piece == key) {
+        Some((_, count)) => *count += 1,
+        None => pieces.push((key, 1)),
+    }
+    true
+}
+
+/// Take one piece out of a side's hand
+pub fn take_from_hand(hand: &mut Hand, key: &str, gote: bool) -> Option<String> {
+    let pieces = if gote { &mut hand.gote } else { &mut hand.sente };
+    let index = pieces.iter().position(|(piece, _)| piece == key)?;
+    pieces[index].1 -= 1;
+    if pieces[index].1 == 0 {
+        pieces.remove(index);
+    }
+    Some(key.to_string())
+}
+
+/// Give a piece to the other side
+pub fn flip_owner(key: &str) -> String {
+    opposite_side_key(key)
+}
+
+/// The other side to move
+pub fn toggle_side(side: &str) -> &'static str {
+    if side == "w" {
+        "b"
+    } else {
+        "w"
+    }
+}
+
+/// Side to move of a SFEN/FEN, or the variant's first mover if missing
+pub fn side_to_move(variant: &dyn Variant, sfen: &str) -> String {
+    match sfen.split_whitespace().nth(1) {
+        Some(side @ ("b" | "w")) => side.to_string(),
+        _ if variant.family() == Family::Shogi => "b".to_string(),
+        _ => "w".to_string(),
+    }
+}
+
+/// Canonical SFEN (shogi) or FEN (chess, xiangqi) of an edited position
+///
+/// Castling rights and move numbers are kept from `original`; en passant squares and
+/// the halfmove clock are reset, since edits can invalidate them.
+pub fn position_sfen(
+    variant: &dyn Variant,
+    board: &[Vec<Option<Piece>>],
+    hand: &Hand,
+    side: &str,
+    original: &str,
+) -> String {
+    let fields: Vec<&str> = original.split_whitespace().collect();
+    let rows: Vec<String> = board.iter().map(|row| board_row(row)).collect();
+    let board = rows.join("/");
+
+    match variant.family() {
+        Family::Shogi => {
+            let move_number = fields.get(3).unwrap_or(&"1");
+            format!("{} {} {} {}", board, side, hand_field(variant, hand), move_number)
+        }
+        Family::Chess => {
+            let pockets = if variant.has_hand() {
+                let pieces = sorted_hand(variant, hand);
+                let letters: String = pieces.iter().map(|(key, count)| key.repeat(*count as usize)).collect();
+                format!("[{}]", letters)
+            } else {
+                String::new()
+            };
+            let castling = fields.get(2).unwrap_or(&"-");
+            let move_number = fields.get(5).unwrap_or(&"1");
+            format!("{}{} {} {} - 0 {}", board, pockets, side, castling, move_number)
+        }
+        Family::Xiangqi => {
+            let move_number = fields.get(5).unwrap_or(&"1");
+            format!("{} {} - - 0 {}", board, side, move_number)
+        }
+    }
+}
+
+/// One board row with runs of empty squares counted and multi-letter codes in parentheses
+fn board_row(row: &[Option<Piece>]) -> String {
+    let mut text = String::new();
+    let mut empty = 0;
+    for square in row {
+        let Some(piece) = square else {
+            empty += 1;
+            continue;
+        };
+        if empty > 0 {
+            text.push_str(&empty.to_string());
+            empty = 0;
+        }
+        let (prefix, code) = match piece.sfen_key.strip_prefix('+') {
+            Some(code) => ("+", code),
+            None => ("", piece.sfen_key.as_str()),
+        };
+        text.push_str(prefix);
+        if code.chars().count() > 1 {
+            text.push_str(&format!("({})", code));
+        } else {
+            text.push_str(code);
+        }
+    }
+    if empty > 0 {
+        text.push_str(&empty.to_string());
+    }
+    text
+}
+
+/// SFEN hand field: counts before letters, first side first, in piece letter order
+fn hand_field(variant: &dyn Variant, hand: &Hand) -> String {
+    let pieces = sorted_hand(variant, hand);
+    if pieces.is_empty() {
+        return "-".to_string();
+    }
+    pieces
+        .iter()
+        .map(|(key, count)| if *count > 1 { format!("{}{}", count, key) } else { key.clone() })
+        .collect()
+}
+
+/// Both hands, first side first, each in the variant's piece letter order
+fn sorted_hand(variant: &dyn Variant, hand: &Hand) -> Vec<(String, u32)> {
+    let order = |key: &String| variant.piece_letters().find(&key.to_uppercase()).unwrap_or(usize::MAX);
+    let mut pieces = Vec::new();
+    for side in [&hand.sente, &hand.gote] {
+        let mut side = side.clone();
+        side.sort_by_key(|(key, _)| order(key));
+        pieces.extend(side);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sfen::parse_sfen;
+    use crate::variant::{parse_position_hand, CHESS, CHU_SHOGI, CRAZYHOUSE, KYOTO_SHOGI, STANDARD_SHOGI, XIANGQI};
+
+    /// Parse a position the way the app does and write it back
+    fn round_trip(variant: &dyn Variant, sfen: &str) -> String {
+        let (files, ranks) = variant.dimensions();
+        let mut board = parse_sfen(sfen, files, ranks).unwrap();
+        for piece in board.iter_mut().flatten().flatten() {
+            piece.sfen_key = variant.normalize_key(&piece.sfen_key);
+        }
+        let hand = parse_position_hand(variant, sfen);
+        position_sfen(variant, &board, &hand, &side_to_move(variant, sfen), sfen)
+    }
+
+    #[test]
+    fn start_positions_round_trip() {
+        for variant in [&STANDARD_SHOGI as &dyn Variant, &CHESS, &CHU_SHOGI, &XIANGQI] {
+            assert_eq!(round_trip(variant, variant.start_position()), variant.start_position());
+        }
+    }
+
+    #[test]
+    fn shogi_hands_are_written_in_canonical_order() {
+        let sfen = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w S2Pb3p 12";
+        assert_eq!(round_trip(&STANDARD_SHOGI, sfen), sfen);
+
+        let unordered = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w 3pb2PS 12";
+        assert_eq!(round_trip(&STANDARD_SHOGI, unordered), sfen);
+    }
+
+    #[test]
+    fn chess_keeps_castling_and_resets_en_passant() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 5 7";
+        let expected = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 7";
+        assert_eq!(round_trip(&CHESS, fen), expected);
+    }
+
+    #[test]
+    fn crazyhouse_pockets_are_written_in_brackets() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR[QNnn] w KQkq - 0 3";
+        assert_eq!(round_trip(&CRAZYHOUSE, fen), fen);
+    }
+
+    #[test]
+    fn multi_digit_empty_runs() {
+        let sfen = "12/5k6/12/12/12/12/12/12/12/12/6K5/12 b - 1";
+        assert_eq!(round_trip(&CHU_SHOGI, sfen), sfen);
+    }
+
+    #[test]
+    fn promotion_and_ownership_on_a_square() {
+        let mut board = parse_sfen("p", 1, 1).unwrap();
+        let piece = board[0][0].as_mut().unwrap();
+
+        piece.sfen_key = toggle_promotion(&STANDARD_SHOGI, &piece.sfen_key).unwrap();
+        assert_eq!(piece.sfen_key, "+p");
+        piece.sfen_key = flip_owner(&piece.sfen_key);
+        assert_eq!(piece.sfen_key, "+P");
+        piece.sfen_key = toggle_promotion(&STANDARD_SHOGI, &piece.sfen_key).unwrap();
+        assert_eq!(piece.sfen_key, "P");
+
+        assert_eq!(toggle_promotion(&STANDARD_SHOGI, "G"), None);
+        assert_eq!(toggle_promotion(&STANDARD_SHOGI, "k"), None);
+        assert_eq!(toggle_promotion(&KYOTO_SHOGI, "S").as_deref(), Some("+S"));
+        assert_eq!(toggle_promotion(&CHESS, "P"), None);
+    }
+
+    #[test]
+    fn hands_refuse_kings_and_foreign_pieces() {
+        let mut hand = Hand::default();
+        assert!(!add_to_hand(&STANDARD_SHOGI, &mut hand, "K", false));
+        assert!(!add_to_hand(&STANDARD_SHOGI, &mut hand, "Q", false));
+        assert!(add_to_hand(&STANDARD_SHOGI, &mut hand, "+r", false));
+        assert!(add_to_hand(&STANDARD_SHOGI, &mut hand, "R", false));
+        assert_eq!(hand.sente, [("R".to_string(), 2)]);
+        assert!(hand.gote.is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+//! Interpreting clipboard text as a position
+
+use crate::sfen::regular_board_dimensions;
+use crate::variant::{find_variant_by_name, is_known_board, Variant, STANDARD_SHOGI};
+
+/// Gote's (the handicap giver's) pieces of the standard shogi handicaps, rows 1-3
+const HANDICAPS: [(&str, &[&str], &str); 10] = [
+    ("香落ち", &["lance", "kyoochi", "left lance"], "lnsgkgsn1/1r5b1/ppppppppp"),
+    ("右香落ち", &["right lance", "migikyoochi"], "1nsgkgsnl/1r5b1/ppppppppp"),
+    ("角落ち", &["bishop", "kakuochi"], "lnsgkgsnl/1r7/ppppppppp"),
+    ("飛車落ち", &["rook", "hishaochi"], "lnsgkgsnl/7b1/ppppppppp"),
+    ("飛香落ち", &["rook lance", "rook and lance", "hikyoochi"], "lnsgkgsn1/7b1/ppppppppp"),
+    ("二枚落ち", &["2 piece", "two piece", "nimaiochi"], "lnsgkgsnl/9/ppppppppp"),
+    ("四枚落ち", &["4 piece", "four piece", "yonmaiochi"], "1nsgkgsn1/9/ppppppppp"),
+    ("六枚落ち", &["6 piece", "six piece", "rokumaiochi"], "2sgkgs2/9/ppppppppp"),
+    ("八枚落ち", &["8 piece", "eight piece", "hachimaiochi"], "3gkg3/9/ppppppppp"),
+    ("十枚落ち", &["10 piece", "ten piece", "jumaiochi"], "4k4/9/ppppppppp"),
+];
+
+/// Sente's untouched half of the board in every handicap, with gote to move
+const HANDICAP_REST: &str = "9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL w - 1";
+
+/// A position read from text, with the variant and a display name given alongside it (if any)
+pub struct Input {
+    pub sfen: String,
+    pub variant_hint: Option<&'static dyn Variant>,
+    pub name: Option<&'static str>,
+}
+
+/// Why no position could be shown, with the clipboard or argument text that was examined (if any)
+pub struct NoPosition {
+    pub reason: String,
+    pub text: Option<String>,
+}
+
+/// Find a position in text, honoring PGN-style `[FEN "..."]` and `[Variant "..."]` headers
+///
+/// A shogi handicap name (e.g. "香落ち", "lance", "2-piece") expands to its start position
+/// with gote to move. Otherwise the first valid board in the text is taken, along with the
+/// fields that follow it, after URL-decoding (so analysis and kifu links work too).
+pub fn parse_input(text: &str) -> Option<Input> {
+    if let Some((name, sfen)) = handicap_position(text) {
+        return Some(Input {
+            sfen,
+            variant_hint: Some(&STANDARD_SHOGI),
+            name: Some(name),
+        });
+    }
+
+    let header_hint = header_value(text, "Variant").and_then(find_variant_by_name);
+    if let Some(fen) = header_value(text, "FEN").or_else(|| header_value(text, "SFEN")) {
+        return Some(Input {
+            sfen: fen.to_string(),
+            variant_hint: header_hint,
+            name: None,
+        });
+    }
+
+    let (sfen, url_hint) = find_position(&percent_decode(text))?;
+    Some(Input {
+        sfen,
+        variant_hint: header_hint.or(url_hint),
+        name: None,
+    })
+}
+
+/// All positions in text: one per line when several lines hold a position (as in problem
+/// sets), otherwise the single position found by [`parse_input`]
+///
+/// Text with a `[FEN "..."]` header is always taken as a single position.
+pub fn parse_inputs(text: &str) -> Vec<Input> {
+    let has_header = header_value(text, "FEN").or_else(|| header_value(text, "SFEN")).is_some();
+    if !has_header {
+        let lines: Vec<Input> = text.lines().filter_map(parse_input).collect();
+        if lines.len() > 1 {
+            return lines;
+        }
+    }
+    parse_input(text).into_iter().collect()
+}
+
+/// Characters that can make up a board field, plus `-` so that `+`-separated fields stay attached
+fn is_scan_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || "+-/()[]~".contains(ch)
+}
+
+/// First valid position in decoded text, with a variant named in the URL path before it
+///
+/// Underscores count as spaces, as in Lichess and Lishogi URLs.
+fn find_position(text: &str) -> Option<(String, Option<&'static dyn Variant>)> {
+    let text = text.replace('_', " ");
+    let tokens: Vec<&str> = text
+        .split(|ch: char| ch.is_whitespace() || "\"'<>,;&=?#".contains(ch))
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    for (index, token) in tokens.iter().enumerate() {
+        for run in token.split(|ch| !is_scan_char(ch)).filter(|run| run.contains('/')) {
+            if let Some((board, attached, hint)) = find_board(run) {
+                let fields = if attached.is_empty() {
+                    following_fields(&tokens[index + 1..])
+                } else {
+                    attached
+                };
+                let sfen = std::iter::once(board).chain(fields).collect::<Vec<_>>().join(" ");
+                return Some((sfen, hint));
+            }
+        }
+    }
+    None
+}
+
+/// Longest valid board in a run of `/`-separated segments, with any `+`-separated fields
+/// attached to it (as in form-encoded query strings) and a variant named by an earlier segment
+///
+/// Boards reaching furthest right win, since URL path words (e.g. "standard") can pass for
+/// rows; a ninth-row crazyhouse pocket is only considered when no plain board ends there.
+fn find_board(run: &str) -> Option<(String, Vec<String>, Option<&'static dyn Variant>)> {
+    // `+b+-+1`: split before the side to move, which must follow the last row
+    let last_row = run.rfind('/').unwrap_or(0);
+    let mut candidates: Vec<(&str, Vec<String>)> = run[last_row..]
+        .match_indices('+')
+        .map(|(at, _)| last_row + at)
+        .filter_map(|at| {
+            let fields: Vec<String> = run[at + 1..].split('+').map(str::to_string).collect();
+            matches!(fields[0].as_str(), "b" | "w").then(|| (&run[..at], fields))
+        })
+        .collect();
+    candidates.push((run, Vec::new()));
+
+    for (text, attached) in candidates {
+        let segments: Vec<&str> = text.split('/').collect();
+        let last = segments.len();
+        // Attached fields belong to the full run, so the board must reach its end
+        let ends = if attached.is_empty() { 2..=last } else { last..=last };
+        for end in ends.rev() {
+            for pocket_row in [false, true] {
+                for start in 0..end.saturating_sub(1) {
+                    let board = segments[start..end].join("/");
+                    if regular_board_dimensions(&board, pocket_row).is_some_and(|dims| is_known_board(&board, dims)) {
+                        let hint = segments[..start].iter().rev().find_map(|segment| find_variant_by_name(segment));
+                        return Some((board, attached, hint));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The fields that plausibly continue a position: side to move, then hand or castling
+/// rights, then up to three move counters or an en passant square
+fn following_fields(tokens: &[&str]) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut tokens = tokens.iter();
+
+    let Some(side) = tokens.next().filter(|side| matches!(**side, "b" | "w")) else {
+        return fields;
+    };
+    fields.push(side.to_string());
+
+    // Hands and castling rights; all-lowercase words of three or more letters are prose
+    let is_hand = |field: &str| {
+        field == "-"
+            || (field.chars().all(|ch| ch.is_ascii_alphanumeric())
+                && (field.len() <= 2 || field.chars().any(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit())))
+    };
+    let Some(hand) = tokens.next().filter(|hand| is_hand(hand)) else {
+        return fields;
+    };
+    fields.push(hand.to_string());
+
+    let is_counter = |field: &str| {
+        field == "-"
+            || field.chars().all(|ch| ch.is_ascii_digit())
+            || (field.len() <= 3
+                && field.starts_with(|ch: char| ch.is_ascii_lowercase())
+                && field[1..].chars().all(|ch| ch.is_ascii_digit()))
+    };
+    fields.extend(tokens.take(3).take_while(|field| is_counter(field)).map(|field| field.to_string()));
+    fields
+}
+
+/// Decode `%XX` escapes, leaving malformed ones as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Japanese name and start SFEN of the handicap named by `text`, if it names one
+///
+/// English names may be written with spaces or hyphens and an optional "handicap" suffix.
+fn handicap_position(text: &str) -> Option<(&'static str, String)> {
+    let normalize = |name: &str| -> String {
+        name.to_lowercase()
+            .replace("handicap", "")
+            .chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .collect()
+    };
+    let wanted = normalize(text);
+    if wanted.is_empty() {
+        return None;
+    }
+
+    HANDICAPS
+        .iter()
+        .find(|(name, aliases, _)| {
+            normalize(name) == wanted || aliases.iter().any(|alias| normalize(alias) == wanted)
+        })
+        .map(|(name, _, gote_rows)| (*name, format!("{}/{}", gote_rows, HANDICAP_REST)))
+}
+
+/// Value of a `[Name "value"]` header line, matching the name case-insensitively
+fn header_value<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.lines().find_map(|line| {
+        let inner = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+        let (tag, value) = inner.split_once(char::is_whitespace)?;
+        if !tag.eq_ignore_ascii_case(name) {
+            return None;
+        }
+        Some(value.trim().trim_matches('"'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHOGI_START: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL";
+
+    /// The position and the id of the hinted variant found in `text`
+    fn scan(text: &str) -> Option<(String, Option<&'static str>)> {
+        parse_input(text).map(|input| (input.sfen, input.variant_hint.map(|variant| variant.id())))
+    }
+
+    #[test]
+    fn sfen_inside_chat_text() {
+        let text = format!("what about {} b - 1 here? it looks drawn", SHOGI_START);
+        assert_eq!(scan(&text), Some((format!("{} b - 1", SHOGI_START), None)));
+    }
+
+    #[test]
+    fn lichess_standard_url() {
+        let url = "https://lichess.org/analysis/standard/rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR_b_KQkq_-_0_1";
+        let expected = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+        assert_eq!(scan(url), Some((expected.to_string(), None)));
+    }
+
+    #[test]
+    fn lichess_crazyhouse_url() {
+        let url = "https://lichess.org/analysis/crazyhouse/rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR[Qn]_b_KQkq_-_0_1";
+        let expected = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR[Qn] b KQkq - 0 1";
+        assert_eq!(scan(url), Some((expected.to_string(), Some("crazyhouse"))));
+    }
+
+    #[test]
+    fn lishogi_minishogi_url() {
+        let url = "https://lishogi.org/analysis/minishogi/rbsgk/4p/5/P4/KGSBR_b_-_1";
+        assert_eq!(scan(url), Some(("rbsgk/4p/5/P4/KGSBR b - 1".to_string(), Some("minishogi"))));
+    }
+
+    #[test]
+    fn form_encoded_query() {
+        let url = format!("https://example.com/kifu?sfen={}+b+-+1&move=3", SHOGI_START.replace('/', "%2F"));
+        assert_eq!(scan(&url), Some((format!("{} b - 1", SHOGI_START), None)));
+    }
+
+    #[test]
+    fn prose_is_not_a_position() {
+        assert!(scan("1/2 and 3/4").is_none());
+        assert!(scan("and/or").is_none());
+        assert!(scan("see https://example.com/a/b/c/d/e/f/g/h/i for details").is_none());
+        assert!(scan("https://github.com/foo/bar/baz/qux").is_none());
+        assert!(scan("https://example.org/api/css/dom/xml").is_none());
+        assert!(scan("https://example.com/guide/intro/setup/build/tests").is_none());
+    }
+
+    #[test]
+    fn url_paths_are_not_positions() {
+        assert!(scan("https://docs.rs/egui/latest/egui/struct.Painter.html").is_none());
+        assert!(scan("https://example.com/blog/2024/05/17/release-notes").is_none());
+        assert!(scan("https://lichess.org/study/abcd1234/efgh5678").is_none());
+    }
+
+    #[test]
+    fn file_paths_are_not_positions() {
+        assert!(scan("usr/local/lib/bin").is_none());
+        assert!(scan("cp /usr/local/lib/bin/tool /opt/pkg/share/doc/").is_none());
+        assert!(scan("see src/app/core/lib/util/io/net/db.rs").is_none());
+    }
+
+    #[test]
+    fn dates_are_not_positions() {
+        assert!(scan("on 2024/05/17 we met").is_none());
+        assert!(scan("12/31/1999 and 1/1/2000").is_none());
+        assert!(scan("3/4/5/6/7/8/9/1/2").is_none());
+    }
+
+    #[test]
+    fn boards_without_pieces_are_not_positions() {
+        assert!(scan("8/8/8/8/8/8/8/8 w - - 0 1").is_none());
+    }
+
+    #[test]
+    fn following_fields_stop_at_prose() {
+        assert_eq!(following_fields(&["b", "-", "1", "nice"]), ["b", "-", "1"]);
+        assert_eq!(following_fields(&["w", "KQkq", "e3", "0", "1"]), ["w", "KQkq", "e3", "0", "1"]);
+        assert_eq!(following_fields(&["b", "and", "then"]), ["b"]);
+        assert!(following_fields(&["is", "nice"]).is_empty());
+    }
+
+    #[test]
+    fn percent_decode_keeps_malformed_escapes() {
+        assert_eq!(percent_decode("a%2Fb%2bc"), "a/b+c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[test]
+    fn every_handicap_expands_to_a_valid_position() {
+        for (name, _, _) in HANDICAPS {
+            let (found, sfen) = handicap_position(name).unwrap();
+            assert_eq!(found, name);
+            assert!(crate::sfen::parse_sfen(&sfen, 9, 9).is_ok(), "{}: {}", name, sfen);
+            assert!(sfen.ends_with(" w - 1"), "{}: {}", name, sfen);
+        }
+    }
+
+    #[test]
+    fn handicap_aliases() {
+        let name = |text: &str| handicap_position(text).map(|(name, _)| name);
+        assert_eq!(name("香落ち"), Some("香落ち"));
+        assert_eq!(name("lance"), Some("香落ち"));
+        assert_eq!(name("Right Lance Handicap"), Some("右香落ち"));
+        assert_eq!(name("2-piece"), Some("二枚落ち"));
+        assert_eq!(name("10 piece handicap"), Some("十枚落ち"));
+        assert_eq!(name("handicap"), None);
+        assert_eq!(name("queen"), None);
+
+        let input = parse_input("2-piece").unwrap();
+        assert_eq!(input.sfen, format!("lnsgkgsnl/9/ppppppppp/{}", HANDICAP_REST));
+        assert_eq!(input.name, Some("二枚落ち"));
+    }
+}
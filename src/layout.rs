@@ -0,0 +1,110 @@
+//! Window and board layout
+
+use eframe::egui::{Pos2, Rect, Vec2};
+
+/// Hand tray width as a fraction of the window width (when hands are shown)
+const HAND_WIDTH_RATIO: f32 = 0.12;
+/// Vertical coordinate margin relative to the horizontal one when hands are shown
+const HAND_VERTICAL_MARGIN: f32 = 1.2;
+/// Share of the window height given to the difference summary in compare mode
+const SUMMARY_HEIGHT_RATIO: f32 = 0.22;
+
+/// Layout proportions, measured in board cells
+struct Proportions {
+    coord: f32,
+    coord_vertical: f32,
+    hand: f32,
+    width: f32,
+    height: f32,
+}
+
+impl Proportions {
+    fn new((files, ranks): (usize, usize), coord_margin: f32, has_hand: bool) -> Self {
+        // Coordinate space is a fraction of the area around the board's longest side
+        let longest = files.max(ranks) as f32;
+        let coord = longest * coord_margin / (1.0 - coord_margin * 2.0);
+
+        let (hand, coord_vertical) = if has_hand {
+            let hand = (longest + coord * 2.0) * HAND_WIDTH_RATIO / (1.0 - HAND_WIDTH_RATIO * 2.0);
+            (hand, coord * HAND_VERTICAL_MARGIN)
+        } else {
+            (0.0, coord)
+        };
+
+        Self {
+            coord,
+            coord_vertical,
+            hand,
+            width: files as f32 + coord * 2.0 + hand * 2.0,
+            height: ranks as f32 + coord_vertical * 2.0,
+        }
+    }
+}
+
+/// Window size for a board of the given dimensions at the given window width
+pub fn window_size(dimensions: (usize, usize), coord_margin: f32, has_hand: bool, width: f32) -> [f32; 2] {
+    let proportions = Proportions::new(dimensions, coord_margin, has_hand);
+    [width, width * proportions.height / proportions.width]
+}
+
+/// Window size for two boards side by side above a difference summary, given the size for one
+pub fn compare_window_size([width, height]: [f32; 2]) -> [f32; 2] {
+    [width * 2.0, height / (1.0 - SUMMARY_HEIGHT_RATIO)]
+}
+
+/// Compare mode areas: (first board, second board, summary)
+pub fn split_compare_area(area: Rect) -> (Rect, Rect, Rect) {
+    let boards_height = area.height() * (1.0 - SUMMARY_HEIGHT_RATIO);
+    let (boards, summary) = area.split_top_bottom_at_y(area.min.y + boards_height);
+    let (first, second) = boards.split_left_right_at_fraction(0.5);
+    (first, second, summary)
+}
+
+/// Board placement within the available area, in points
+pub struct BoardLayout {
+    /// Size of one board cell
+    pub cell_size: f32,
+    /// Top-left corner of the board
+    pub offset: Pos2,
+    /// Board width and height
+    pub board_size: Vec2,
+    /// Horizontal space reserved for coordinates on each side
+    pub coord_space: f32,
+    /// Width of each hand tray area (0 when hands are hidden)
+    pub hand_width: f32,
+}
+
+impl BoardLayout {
+    /// Fit a board of the given dimensions into `area`, centered
+    pub fn new(area: Rect, dimensions: (usize, usize), coord_margin: f32, has_hand: bool) -> Self {
+        let proportions = Proportions::new(dimensions, coord_margin, has_hand);
+        let available = area.size();
+        let cell_size = (available.x / proportions.width).min(available.y / proportions.height);
+
+        let board_size = Vec2::new(dimensions.0 as f32, dimensions.1 as f32) * cell_size;
+        let used = Vec2::new(proportions.width, proportions.height) * cell_size;
+        let margin = (available - used) / 2.0;
+        let offset = Pos2::new(
+            area.min.x + margin.x + (proportions.hand + proportions.coord) * cell_size,
+            area.min.y + margin.y + proportions.coord_vertical * cell_size,
+        );
+
+        Self {
+            cell_size,
+            offset,
+            board_size,
+            coord_space: proportions.coord * cell_size,
+            hand_width: proportions.hand * cell_size,
+        }
+    }
+
+    /// Left edge of the left (gote) hand area
+    pub fn left_hand_x(&self) -> f32 {
+        self.offset.x - self.coord_space - self.hand_width
+    }
+
+    /// Left edge of the right (sente) hand area
+    pub fn right_hand_x(&self) -> f32 {
+        self.offset.x + self.board_size.x + self.coord_space
+    }
+}
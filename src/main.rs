@@ -2,10 +2,20 @@
 
 mod app;
 mod board;
+mod cli;
+mod compare;
 mod config;
+mod editor;
 mod fonts;
+mod input;
+mod layout;
+mod message;
+mod piece_sets;
 mod pieces;
 mod sfen;
+mod textures;
+mod themes;
+mod variant;
 
 use std::path::PathBuf;
 
@@ -13,14 +23,23 @@ use arboard::Clipboard;
 use eframe::egui;
 
 use app::SfenApp;
+use cli::{parse_args, USAGE};
+use compare::compare_positions;
 use config::{load_config, Config};
 use fonts::setup_fonts;
-use sfen::{detect_board_size, is_chess, parse_hand, CHESS_SIZE, MINISHOGI_SIZE};
+use input::{parse_input, parse_inputs, Input, NoPosition};
+use layout::{compare_window_size, window_size};
+use message::MessageApp;
+use sfen::parse_sfen;
+use variant::{detect_variant, find_variant, parse_position_hand, variant_ids, STANDARD_SHOGI};
 
 fn main() -> eframe::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        std::process::exit(1);
+    });
 
-    let (config, assets_path) = if let Some(config_path) = args.get(1).map(PathBuf::from) {
+    let (config, assets_path) = if let Some(config_path) = args.config_path {
         let config = load_config(&config_path);
         let assets_path = config_path
             .parent()
@@ -31,37 +50,91 @@ fn main() -> eframe::Result<()> {
         (Config::default(), PathBuf::from("."))
     };
 
-    let sfen = read_sfen_from_clipboard();
-    let board_size = detect_board_size(&sfen);
-    let hand = parse_hand(&sfen);
-    let has_hand = !is_chess(board_size) && (!hand.sente.is_empty() || !hand.gote.is_empty());
+    let explicit_variant = args.variant.map(|id| {
+        find_variant(&id).unwrap_or_else(|| {
+            eprintln!("Unknown variant '{}', expected one of: {}", id, variant_ids().join(", "));
+            std::process::exit(1);
+        })
+    });
 
-    let base_size = match board_size {
-        MINISHOGI_SIZE => 500.0,
-        CHESS_SIZE => 700.0,
-        _ => 900.0, // Standard shogi
-    };
-    let window_width = base_size * config.scale_factor();
-
-    // Calculate actual height needed based on layout
-    let window_height = if has_hand {
-        let hand_width_ratio = 0.12;
-        let coord_margin_ratio = if board_size == MINISHOGI_SIZE { 0.10 } else { 0.05 };
-        let board_area_ratio = 1.0 - hand_width_ratio * 2.0; // 0.76
-        let coord_space_ratio = board_area_ratio * coord_margin_ratio;
-        let board_ratio = board_area_ratio - coord_space_ratio * 2.0;
-        let vertical_margin_ratio = coord_space_ratio * 1.2;
-        // Height = top_margin + board + bottom_margin
-        window_width * (vertical_margin_ratio * 2.0 + board_ratio)
+    let fallback = explicit_variant.unwrap_or(&STANDARD_SHOGI);
+    let start_position = || vec![(fallback.start_position().to_string(), None)];
+    let inputs = if args.positions.is_empty() {
+        read_sfen_from_clipboard()
     } else {
-        window_width
+        read_sfen_from_args(&args.positions)
     };
+    let inputs = match inputs {
+        Ok(inputs) => inputs,
+        Err(_) if args.start_position => Vec::new(),
+        Err(no_position) => return show_message(no_position, &config),
+    };
+
+    // The first position decides the variant; later ones that don't fit it are skipped
+    let mut variant = match inputs.first() {
+        Some(first) => explicit_variant
+            .or(first.variant_hint)
+            .unwrap_or_else(|| detect_variant(&first.sfen)),
+        None => fallback,
+    };
+    let (files, ranks) = variant.dimensions();
+    let mut first_error = None;
+    let mut positions = Vec::new();
+    for input in inputs {
+        match parse_sfen(&input.sfen, files, ranks) {
+            Ok(_) => positions.push((input.sfen, input.name.map(str::to_string))),
+            // Skipping would pair the wrong positions in compare mode
+            Err(e) if args.compare => {
+                let reason = format!("Invalid {} position: {}", variant.name(), e);
+                return show_message(NoPosition { reason, text: Some(input.sfen) }, &config);
+            }
+            Err(e) => {
+                eprintln!("Skipping invalid {} position '{}': {}", variant.name(), input.sfen, e);
+                first_error.get_or_insert((e, input.sfen));
+            }
+        }
+    }
+    if positions.is_empty() {
+        if let (Some((e, sfen)), false) = (first_error, args.start_position) {
+            let reason = format!("Invalid {} position: {}", variant.name(), e);
+            return show_message(NoPosition { reason, text: Some(sfen) }, &config);
+        }
+        positions = start_position();
+        variant = fallback;
+    }
+
+    let has_hand = variant.has_hand()
+        && positions.iter().any(|(sfen, _)| {
+            let hand = parse_position_hand(variant, sfen);
+            !hand.sente.is_empty() || !hand.gote.is_empty()
+        });
+
+    if args.compare && positions.len() < 2 {
+        let reason = format!("Compare mode needs two positions, found {}", positions.len());
+        let text = positions.into_iter().next().map(|(sfen, _)| sfen);
+        return show_message(NoPosition { reason, text }, &config);
+    }
+
+    let window_width = variant.base_window_size() * config.scale_factor();
+    let mut window_size = window_size(variant.dimensions(), variant.coord_margin(), has_hand, window_width);
+    let comparison = args.compare.then(|| compare_positions(variant, &positions[0].0, &positions[1].0));
+    if let Some(differences) = &comparison {
+        window_size = compare_window_size(window_size);
+        for line in &differences.summary {
+            println!("{}", line);
+        }
+    }
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([window_width, window_height])
+            .with_inner_size(window_size)
             .with_decorations(false)
-            .with_title("SFEN Bro"),
+            .with_title(match positions.as_slice() {
+                _ if args.compare => format!("SFEN Bro - {} (compare)", variant.name()),
+                [(_, Some(status))] => format!("SFEN Bro - {} ({})", variant.name(), status),
+                [_] => format!("SFEN Bro - {}", variant.name()),
+                _ => format!("SFEN Bro - {} ({} positions)", variant.name(), positions.len()),
+            }),
         centered: true,
         ..Default::default()
     };
@@ -71,15 +144,65 @@ fn main() -> eframe::Result<()> {
         options,
         Box::new(move |cc| {
             setup_fonts(&cc.egui_ctx);
-            Ok(Box::new(SfenApp::new(sfen, assets_path, config)))
+            Ok(Box::new(SfenApp::new(positions, variant, comparison, assets_path, config)))
         }),
     )
 }
 
-fn read_sfen_from_clipboard() -> String {
-    Clipboard::new()
-        .and_then(|mut cb| cb.get_text())
-        .unwrap_or_else(|_| {
-            "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1".into()
+/// Find the positions in the clipboard text
+fn read_sfen_from_clipboard() -> Result<Vec<Input>, NoPosition> {
+    let text = Clipboard::new().and_then(|mut cb| cb.get_text()).map_err(|e| NoPosition {
+        reason: format!("Could not read text from the clipboard: {}", e),
+        text: None,
+    })?;
+
+    if text.trim().is_empty() {
+        return Err(NoPosition {
+            reason: "The clipboard is empty".to_string(),
+            text: None,
+        });
+    }
+    let inputs = parse_inputs(&text);
+    if inputs.is_empty() {
+        return Err(NoPosition {
+            reason: "No SFEN or FEN position found in the clipboard text".to_string(),
+            text: Some(text),
+        });
+    }
+    Ok(inputs)
+}
+
+/// Find a position in each command-line argument
+fn read_sfen_from_args(args: &[String]) -> Result<Vec<Input>, NoPosition> {
+    args.iter()
+        .map(|arg| {
+            parse_input(arg).ok_or_else(|| NoPosition {
+                reason: "No SFEN or FEN position found in a --position argument".to_string(),
+                text: Some(arg.clone()),
+            })
         })
+        .collect()
+}
+
+/// Open a window explaining why there is no position to show
+fn show_message(no_position: NoPosition, config: &Config) -> eframe::Result<()> {
+    eprintln!("{}", no_position.reason);
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([520.0 * config.scale_factor(), 240.0 * config.scale_factor()])
+            .with_decorations(false)
+            .with_title("SFEN Bro - No position"),
+        centered: true,
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "SFEN Bro",
+        options,
+        Box::new(move |cc| {
+            setup_fonts(&cc.egui_ctx);
+            Ok(Box::new(MessageApp::new(no_position)))
+        }),
+    )
 }
@@ -0,0 +1,67 @@
+//! Message window shown instead of a board when there is no position
+
+use eframe::egui::{self, RichText};
+
+use crate::input::NoPosition;
+
+/// Longest excerpt of the clipboard text shown, in characters
+const MAX_EXCERPT_CHARS: usize = 300;
+/// Longest excerpt of the clipboard text shown, in lines
+const MAX_EXCERPT_LINES: usize = 6;
+
+/// Application state for the message window
+pub struct MessageApp {
+    reason: String,
+    excerpt: Option<String>,
+    frame_count: u32,
+}
+
+impl MessageApp {
+    /// Create a message window for the given reason and clipboard text
+    pub fn new(no_position: NoPosition) -> Self {
+        Self {
+            reason: no_position.reason,
+            excerpt: no_position.text.as_deref().map(excerpt),
+            frame_count: 0,
+        }
+    }
+}
+
+/// The start of `text`, cut to a few lines and characters, with an ellipsis if cut
+fn excerpt(text: &str) -> String {
+    let text = text.trim();
+    let lines: Vec<&str> = text.lines().collect();
+    let joined = lines[..lines.len().min(MAX_EXCERPT_LINES)].join("\n");
+    let mut chars = joined.chars();
+    let mut excerpt: String = chars.by_ref().take(MAX_EXCERPT_CHARS).collect();
+    if lines.len() > MAX_EXCERPT_LINES || chars.next().is_some() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+impl eframe::App for MessageApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.frame_count = self.frame_count.saturating_add(1);
+
+        // Close on Escape or when window loses focus, like the board window
+        let lost_focus = self.frame_count > 10 && ctx.input(|i| !i.focused);
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) || lost_focus {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("No position to show");
+            ui.label(&self.reason);
+
+            if let Some(excerpt) = &self.excerpt {
+                ui.separator();
+                ui.label("Input:");
+                ui.label(RichText::new(excerpt).monospace());
+            }
+
+            ui.separator();
+            ui.label("Copy a SFEN or FEN and run again, or pass --start-position to show the start position instead.");
+        });
+    }
+}
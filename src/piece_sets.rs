@@ -0,0 +1,80 @@
+//! Bundled piece sets
+
+const WHITE_KING: &[u8] = include_bytes!("../resources/chess/wK.png");
+const WHITE_QUEEN: &[u8] = include_bytes!("../resources/chess/wQ.png");
+const WHITE_ROOK: &[u8] = include_bytes!("../resources/chess/wR.png");
+const WHITE_BISHOP: &[u8] = include_bytes!("../resources/chess/wB.png");
+const WHITE_KNIGHT: &[u8] = include_bytes!("../resources/chess/wN.png");
+const WHITE_PAWN: &[u8] = include_bytes!("../resources/chess/wP.png");
+const WHITE_ARCHBISHOP: &[u8] = include_bytes!("../resources/chess/wA.png");
+const WHITE_CHANCELLOR: &[u8] = include_bytes!("../resources/chess/wC.png");
+const WHITE_FERZ: &[u8] = include_bytes!("../resources/chess/wF.png");
+const WHITE_ALFIL: &[u8] = include_bytes!("../resources/chess/wE.png");
+const BLACK_KING: &[u8] = include_bytes!("../resources/chess/bK.png");
+const BLACK_QUEEN: &[u8] = include_bytes!("../resources/chess/bQ.png");
+const BLACK_ROOK: &[u8] = include_bytes!("../resources/chess/bR.png");
+const BLACK_BISHOP: &[u8] = include_bytes!("../resources/chess/bB.png");
+const BLACK_KNIGHT: &[u8] = include_bytes!("../resources/chess/bN.png");
+const BLACK_PAWN: &[u8] = include_bytes!("../resources/chess/bP.png");
+const BLACK_ARCHBISHOP: &[u8] = include_bytes!("../resources/chess/bA.png");
+const BLACK_CHANCELLOR: &[u8] = include_bytes!("../resources/chess/bC.png");
+const BLACK_FERZ: &[u8] = include_bytes!("../resources/chess/bF.png");
+const BLACK_ALFIL: &[u8] = include_bytes!("../resources/chess/bE.png");
+
+/// Default chess piece images (FEN key -> PNG bytes)
+///
+/// The archbishop (`A`) and chancellor (`C`) combine the knight with a bishop or rook.
+pub const DEFAULT_CHESS_PIECES: [(&str, &[u8]); 16] = [
+    ("K", WHITE_KING),
+    ("Q", WHITE_QUEEN),
+    ("R", WHITE_ROOK),
+    ("B", WHITE_BISHOP),
+    ("N", WHITE_KNIGHT),
+    ("P", WHITE_PAWN),
+    ("A", WHITE_ARCHBISHOP),
+    ("C", WHITE_CHANCELLOR),
+    ("k", BLACK_KING),
+    ("q", BLACK_QUEEN),
+    ("r", BLACK_ROOK),
+    ("b", BLACK_BISHOP),
+    ("n", BLACK_KNIGHT),
+    ("p", BLACK_PAWN),
+    ("a", BLACK_ARCHBISHOP),
+    ("c", BLACK_CHANCELLOR),
+];
+
+/// Makruk piece images: the met (`M`) is a small queen, the khon (`S`) a bishop
+pub const MAKRUK_PIECES: [(&str, &[u8]); 12] = [
+    ("K", WHITE_KING),
+    ("M", WHITE_FERZ),
+    ("S", WHITE_BISHOP),
+    ("N", WHITE_KNIGHT),
+    ("R", WHITE_ROOK),
+    ("P", WHITE_PAWN),
+    ("k", BLACK_KING),
+    ("m", BLACK_FERZ),
+    ("s", BLACK_BISHOP),
+    ("n", BLACK_KNIGHT),
+    ("r", BLACK_ROOK),
+    ("p", BLACK_PAWN),
+];
+
+/// Shatranj piece images: the ferz (`Q` or `F`) is a small queen, the alfil (`B` or `E`) a small bishop
+pub const SHATRANJ_PIECES: [(&str, &[u8]); 16] = [
+    ("K", WHITE_KING),
+    ("Q", WHITE_FERZ),
+    ("F", WHITE_FERZ),
+    ("B", WHITE_ALFIL),
+    ("E", WHITE_ALFIL),
+    ("N", WHITE_KNIGHT),
+    ("R", WHITE_ROOK),
+    ("P", WHITE_PAWN),
+    ("k", BLACK_KING),
+    ("q", BLACK_FERZ),
+    ("f", BLACK_FERZ),
+    ("b", BLACK_ALFIL),
+    ("e", BLACK_ALFIL),
+    ("n", BLACK_KNIGHT),
+    ("r", BLACK_ROOK),
+    ("p", BLACK_PAWN),
+];
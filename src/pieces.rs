@@ -5,7 +5,41 @@ use std::collections::HashMap;
 use eframe::egui::{self, Color32, FontFamily, FontId, Pos2, Rect, Stroke, TextureHandle, Vec2};
 use egui::epaint::TextShape;
 
-use crate::sfen::{fen_to_unicode, is_chess, is_gote, sfen_to_kanji, Piece};
+use crate::sfen::{is_gote, Piece};
+use crate::variant::{PieceStyle, Variant};
+
+/// Colors for pieces drawn without images
+#[derive(Clone, Copy)]
+pub struct PieceColors {
+    /// Kanji and symbol pieces
+    pub text: Color32,
+    /// Disc pieces of the side written in uppercase
+    pub first_side: Color32,
+    /// Disc pieces of the side written in lowercase
+    pub second_side: Color32,
+    /// Disc fill
+    pub disc: Color32,
+}
+
+/// Colors of a hand tray
+#[derive(Clone, Copy)]
+pub struct TrayColors {
+    pub fill: Color32,
+    pub border: Color32,
+    /// Piece counts and text pieces
+    pub text: Color32,
+}
+
+/// Where a hand is drawn
+#[derive(Clone, Copy)]
+pub struct HandPlacement {
+    /// Top-left corner of the first piece
+    pub top_left: Pos2,
+    /// Size of one piece cell
+    pub cell_size: f32,
+    /// Gote's (or black's) hand, whose pieces are rotated
+    pub gote: bool,
+}
 
 /// Draw all pieces on the board
 pub fn draw_pieces(
@@ -14,11 +48,10 @@ pub fn draw_pieces(
     cell_size: f32,
     board: &[Vec<Option<Piece>>],
     textures: &HashMap<String, TextureHandle>,
-    text_color: Color32,
-    board_size: usize,
+    colors: PieceColors,
+    variant: &dyn Variant,
 ) {
     let piece_size = cell_size;
-    let use_textures = !is_chess(board_size);
 
     for (row, row_pieces) in board.iter().enumerate() {
         for (col, piece_opt) in row_pieces.iter().enumerate() {
@@ -28,36 +61,62 @@ pub fn draw_pieces(
                     offset.y + (row as f32 + 0.5) * cell_size,
                 );
 
-                if use_textures {
-                    if let Some(texture) = textures.get(&piece.sfen_key) {
-                        let rect = Rect::from_center_size(center, Vec2::splat(piece_size));
-                        painter.image(
-                            texture.id(),
-                            rect,
-                            Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
-                            Color32::WHITE,
-                        );
-                        continue;
-                    }
+                if let Some(texture) = textures.get(&piece.sfen_key) {
+                    let rect = Rect::from_center_size(center, Vec2::splat(piece_size));
+                    painter.image(
+                        texture.id(),
+                        rect,
+                        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                        Color32::WHITE,
+                    );
+                    continue;
                 }
-                draw_piece_as_text(painter, center, cell_size, &piece.sfen_key, text_color, board_size);
+                draw_piece_as_text(painter, center, cell_size, &piece.sfen_key, colors, variant);
             }
         }
     }
 }
 
 /// Draw a piece using text when no texture is available
-fn draw_piece_as_text(painter: &egui::Painter, center: Pos2, cell_size: f32, piece_key: &str, color: Color32, board_size: usize) {
+fn draw_piece_as_text(painter: &egui::Painter, center: Pos2, cell_size: f32, piece_key: &str, colors: PieceColors, variant: &dyn Variant) {
     let font_size = cell_size * 0.7;
+    let color = colors.text;
 
-    if is_chess(board_size) {
+    if variant.piece_style() == PieceStyle::Symbol {
         // Chess: Unicode symbols, no rotation
-        let symbol = fen_to_unicode(piece_key);
+        let symbol = variant.piece_label(piece_key);
         let font = FontId::proportional(font_size);
         painter.text(center, egui::Align2::CENTER_CENTER, symbol, font, color);
+    } else if variant.piece_style() == PieceStyle::Disc {
+        // Xiangqi: character on a disc, colored by side, no rotation
+        let side_color = if is_gote(piece_key) { colors.second_side } else { colors.first_side };
+        let radius = cell_size * 0.44;
+        painter.circle(center, radius, colors.disc, Stroke::new(cell_size * 0.03, side_color));
+        painter.circle_stroke(center, radius * 0.82, Stroke::new(1.0, side_color));
+
+        let font = FontId::new(cell_size * 0.5, FontFamily::Name("YujiMai".into()));
+        painter.text(center, egui::Align2::CENTER_CENTER, variant.piece_label(piece_key), font, side_color);
+    } else if variant.piece_style() == PieceStyle::Octagon {
+        // Janggi: character on an octagon sized by rank, colored by side, no rotation
+        let side_color = if is_gote(piece_key) { colors.second_side } else { colors.first_side };
+        let radius = cell_size * octagon_scale(piece_key);
+        let points = (0..8)
+            .map(|i| {
+                let angle = std::f32::consts::PI * (i as f32 + 0.5) / 4.0;
+                center + Vec2::angled(angle) * radius
+            })
+            .collect();
+        painter.add(egui::Shape::convex_polygon(
+            points,
+            colors.disc,
+            Stroke::new(cell_size * 0.03, side_color),
+        ));
+
+        let font = FontId::new(radius * 1.1, FontFamily::Name("YujiMai".into()));
+        painter.text(center, egui::Align2::CENTER_CENTER, variant.piece_label(piece_key), font, side_color);
     } else {
         // Shogi: Kanji with YujiMai font, gote pieces rotated
-        let kanji = sfen_to_kanji(piece_key);
+        let kanji = variant.piece_label(piece_key);
         let font = FontId::new(font_size, FontFamily::Name("YujiMai".into()));
 
         let is_gote_piece = is_gote(piece_key);
@@ -85,18 +144,27 @@ fn draw_piece_as_text(painter: &egui::Painter, center: Pos2, cell_size: f32, pie
     }
 }
 
+/// Janggi piece radius relative to the cell: large general, small guards and soldiers
+fn octagon_scale(piece_key: &str) -> f32 {
+    match piece_key.to_ascii_uppercase().as_str() {
+        "K" => 0.47,
+        "A" | "P" => 0.34,
+        _ => 0.41,
+    }
+}
+
 /// Draw pieces in hand for a single player
-/// `is_gote_hand` determines orientation (gote pieces are rotated)
+/// `placement.gote` determines orientation (gote pieces are rotated)
 pub fn draw_hand(
     painter: &egui::Painter,
-    top_left: Pos2,
-    cell_size: f32,
+    placement: HandPlacement,
     hand: &[(String, u32)],
     textures: &HashMap<String, TextureHandle>,
-    text_color: Color32,
-    border_color: Color32,
-    is_gote_hand: bool,
+    tray: TrayColors,
+    variant: &dyn Variant,
 ) {
+    let HandPlacement { top_left, cell_size, gote: is_gote_hand } = placement;
+    let text_color = tray.text;
     let piece_size = cell_size * 0.9;
     let count_font_size = cell_size * 0.35;
     let count_font = FontId::proportional(count_font_size);
@@ -109,7 +177,8 @@ pub fn draw_hand(
         Pos2::new(top_left.x - padding, top_left.y - padding),
         Vec2::new(rect_width, rect_height),
     );
-    painter.rect_stroke(rect, 2.0, Stroke::new(1.0, border_color), egui::StrokeKind::Outside);
+    painter.rect_filled(rect, 2.0, tray.fill);
+    painter.rect_stroke(rect, 2.0, Stroke::new(1.0, tray.border), egui::StrokeKind::Outside);
 
     for (idx, (sfen_key, count)) in hand.iter().enumerate() {
         let center = Pos2::new(
@@ -132,9 +201,14 @@ pub fn draw_hand(
                 Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
                 Color32::WHITE,
             );
+        } else if variant.piece_style() == PieceStyle::Symbol {
+            // Fall back to an unrotated symbol (chess pockets)
+            let font = FontId::proportional(cell_size * 0.65);
+            let symbol = variant.piece_label(&display_key);
+            painter.text(center, egui::Align2::CENTER_CENTER, symbol, font, text_color);
         } else {
             // Fall back to kanji
-            draw_hand_piece_kanji(painter, center, cell_size, &display_key, text_color, is_gote_hand);
+            draw_hand_piece_kanji(painter, center, cell_size, variant.piece_label(&display_key), text_color, is_gote_hand);
         }
 
         // Draw count if more than 1
@@ -153,12 +227,11 @@ fn draw_hand_piece_kanji(
     painter: &egui::Painter,
     center: Pos2,
     cell_size: f32,
-    piece_key: &str,
+    kanji: &str,
     color: Color32,
     is_gote_hand: bool,
 ) {
     let font_size = cell_size * 0.65;
-    let kanji = sfen_to_kanji(piece_key);
     let font = FontId::new(font_size, FontFamily::Name("YujiMai".into()));
 
     let galley = painter.layout_no_wrap(kanji.to_string(), font, color);
@@ -1,8 +1,6 @@
 //! Board notation parsing and piece representation
 
-pub const STANDARD_SHOGI_SIZE: usize = 9;
-pub const CHESS_SIZE: usize = 8;
-pub const MINISHOGI_SIZE: usize = 5;
+use std::fmt;
 
 /// Convert SFEN piece character to kanji representation (for shogi)
 pub fn sfen_to_kanji(sfen_key: &str) -> &'static str {
@@ -52,24 +50,22 @@ pub fn is_gote(sfen_key: &str) -> bool {
     ch.is_lowercase()
 }
 
-/// Check if board size is chess
-pub fn is_chess(board_size: usize) -> bool {
-    board_size == CHESS_SIZE
-}
-
-/// Detect board size from SFEN/FEN string
-pub fn detect_board_size(sfen: &str) -> usize {
-    let board_part = sfen.split_whitespace().next().unwrap_or(sfen);
-    let row_count = board_part.split('/').count();
-    match row_count {
-        MINISHOGI_SIZE => MINISHOGI_SIZE,
-        CHESS_SIZE => CHESS_SIZE,
-        _ => STANDARD_SHOGI_SIZE,
-    }
+/// Get the same piece for the other side (e.g. "+R" -> "+r")
+pub fn opposite_side_key(sfen_key: &str) -> String {
+    sfen_key
+        .chars()
+        .map(|ch| {
+            if ch.is_uppercase() {
+                ch.to_ascii_lowercase()
+            } else {
+                ch.to_ascii_uppercase()
+            }
+        })
+        .collect()
 }
 
 /// A piece on the board
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Piece {
     pub sfen_key: String,
 }
@@ -114,51 +110,345 @@ pub fn parse_hand(sfen: &str) -> Hand {
     Hand { sente, gote }
 }
 
-/// Parse SFEN board position into a 2D vector
-pub fn parse_sfen(sfen: &str, board_size: usize) -> Vec<Vec<Option<Piece>>> {
-    let mut board = vec![vec![None; board_size]; board_size];
+/// Parse crazyhouse pockets (`[Qn]` suffix or ninth row) from a FEN string into hands
+///
+/// Repeated letters are counted; promoted markers (`~`) and other characters are ignored.
+pub fn parse_pockets(fen: &str) -> Hand {
+    let board_part = fen.split_whitespace().next().unwrap_or(fen);
+    let mut hand = Hand::default();
 
-    let board_part = sfen.split_whitespace().next().unwrap_or(sfen);
-    let rows: Vec<&str> = board_part.split('/').collect();
+    for ch in split_pocket(board_part).1.unwrap_or("").chars() {
+        if !ch.is_alphabetic() {
+            continue;
+        }
+        let pieces = if ch.is_uppercase() {
+            &mut hand.sente
+        } else {
+            &mut hand.gote
+        };
+        let key = ch.to_string();
+        match pieces.iter_mut().find(|(piece, _)| *piece == key) {
+            Some((_, count)) => *count += 1,
+            None => pieces.push((key, 1)),
+        }
+    }
 
-    if rows.len() != board_size {
-        return board;
+    hand
+}
+
+/// Whether a FEN string carries crazyhouse pockets
+pub fn has_pockets(fen: &str) -> bool {
+    let board_part = fen.split_whitespace().next().unwrap_or(fen);
+    split_pocket(board_part).1.is_some()
+}
+
+/// Split a board field into its rows and crazyhouse pocket, if any
+///
+/// The pocket is either a `[...]` suffix or a ninth row of letters after eight 8-square rows.
+fn split_pocket(board_part: &str) -> (Vec<&str>, Option<&str>) {
+    if let Some((rows, pocket)) = board_part.split_once('[') {
+        return (rows.split('/').collect(), Some(pocket.trim_end_matches(']')));
     }
 
-    for (row_idx, row) in rows.iter().enumerate() {
-        let mut col = 0;
-        let mut promoted = false;
+    let mut rows: Vec<&str> = board_part.split('/').collect();
+    let is_pocket_row = rows.len() == 9
+        && rows[..8].iter().all(|row| parse_row(row).0 == 8)
+        && rows[8].chars().all(|ch| ch.is_alphabetic() || ch == '~');
+    if is_pocket_row {
+        let pocket = rows.pop();
+        return (rows, pocket);
+    }
+    (rows, None)
+}
+
+/// Board squares (row, col) of the rooks that keep castling rights
+///
+/// Reads the third FEN field in standard (`KQkq`), X-FEN or Shredder-FEN (`HAha`) form:
+/// `K`/`Q` stand for the outermost rook on that side of the king, and file letters
+/// name the rook's file directly.
+pub fn parse_castling(fen: &str, board: &[Vec<Option<Piece>>]) -> Vec<(usize, usize)> {
+    let castling = fen.split_whitespace().nth(2).unwrap_or("-");
+    let mut rooks = Vec::new();
+
+    for ch in castling.chars() {
+        let is_white = ch.is_ascii_uppercase();
+        let row = if is_white { board.len().saturating_sub(1) } else { 0 };
+        let Some(back_rank) = board.get(row) else {
+            continue;
+        };
+        let (king, rook) = if is_white { ("K", "R") } else { ("k", "r") };
+        let is_piece = |col: &usize, key: &str| {
+            back_rank[*col].as_ref().is_some_and(|piece| piece.sfen_key == key)
+        };
+        let Some(king_col) = (0..back_rank.len()).find(|col| is_piece(col, king)) else {
+            continue;
+        };
+
+        let rook_col = match ch.to_ascii_lowercase() {
+            'k' => (king_col + 1..back_rank.len()).rev().find(|col| is_piece(col, rook)),
+            'q' => (0..king_col).find(|col| is_piece(col, rook)),
+            file @ 'a'..='z' => Some(file as usize - 'a' as usize).filter(|col| {
+                *col < back_rank.len() && is_piece(col, rook)
+            }),
+            _ => None,
+        };
+        if let Some(col) = rook_col {
+            rooks.push((row, col));
+        }
+    }
 
-        for ch in row.chars() {
-            if col >= board_size {
-                break;
+    rooks
+}
+
+/// Error in the board part of an SFEN/FEN string
+#[derive(Debug)]
+pub enum SfenError {
+    /// The number of `/`-separated rows differs from the board's rank count
+    RankCount { expected: usize, found: usize },
+    /// A row describes more squares than the board has files
+    RowOverflow { rank: usize, squares: usize, files: usize },
+}
+
+impl fmt::Display for SfenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SfenError::RankCount { expected, found } => {
+                write!(f, "expected {} ranks, found {}", expected, found)
             }
+            SfenError::RowOverflow { rank, squares, files } => write!(
+                f,
+                "row {} describes {} squares but the board has {} files",
+                rank, squares, files
+            ),
+        }
+    }
+}
+
+/// Parse one SFEN/FEN row into its width in squares and its pieces with their columns
+///
+/// Empty-square counts may span several digits (e.g. `12` on a 12-file board), and
+/// multi-letter piece codes are written in parentheses (e.g. `(DE)`, `+(de)`).
+fn parse_row(row: &str) -> (usize, Vec<(usize, Piece)>) {
+    let mut pieces = Vec::new();
+    let mut col: usize = 0;
+    let mut empty_count: usize = 0;
+    let mut promoted = false;
+    let mut chars = row.chars();
+
+    while let Some(ch) = chars.next() {
+        if let Some(digit) = ch.to_digit(10) {
+            empty_count = empty_count.saturating_mul(10).saturating_add(digit as usize);
+            promoted = false;
+            continue;
+        }
+
+        col = col.saturating_add(empty_count);
+        empty_count = 0;
 
-            match ch {
-                '+' => {
-                    promoted = true;
-                    continue;
-                }
-                '1'..='9' => {
-                    col += ch.to_digit(10).unwrap() as usize;
-                    promoted = false;
-                }
-                _ if ch.is_alphabetic() => {
-                    let sfen_key = if promoted {
-                        format!("+{}", ch)
-                    } else {
-                        ch.to_string()
-                    };
-                    board[row_idx][col] = Some(Piece { sfen_key });
-                    col += 1;
-                    promoted = false;
-                }
-                _ => {
-                    promoted = false;
-                }
+        match ch {
+            '+' => {
+                promoted = true;
+                continue;
             }
+            '(' => {
+                let code: String = chars.by_ref().take_while(|&ch| ch != ')').collect();
+                let sfen_key = if promoted { format!("+{}", code) } else { code };
+                pieces.push((col, Piece { sfen_key }));
+                col = col.saturating_add(1);
+            }
+            _ if ch.is_alphabetic() => {
+                let sfen_key = if promoted {
+                    format!("+{}", ch)
+                } else {
+                    ch.to_string()
+                };
+                pieces.push((col, Piece { sfen_key }));
+                col = col.saturating_add(1);
+            }
+            _ => {}
         }
+        promoted = false;
+    }
+
+    (col.saturating_add(empty_count), pieces)
+}
+
+/// Dimensions of a board field whose rows all describe the same number of squares
+///
+/// Returns `None` for ragged boards, boards without pieces, or characters that cannot
+/// appear in a board field. `pocket_row` allows the ninth-row crazyhouse pocket form.
+pub fn regular_board_dimensions(board_part: &str, pocket_row: bool) -> Option<(usize, usize)> {
+    let is_board_char = |ch: char| ch.is_ascii_alphanumeric() || "+/()[]~".contains(ch);
+    if !board_part.chars().all(is_board_char) || !board_part.chars().any(|ch| ch.is_ascii_alphabetic()) {
+        return None;
     }
 
-    board
+    let (rows, pocket) = split_pocket(board_part);
+    if pocket.is_some() && !pocket_row && !board_part.contains('[') {
+        return None;
+    }
+    let files = parse_row(rows.first()?).0;
+    let regular = rows.len() >= 2 && files > 0 && rows.iter().all(|row| parse_row(row).0 == files);
+    regular.then_some((files, rows.len()))
+}
+
+/// Board dimensions as (files, ranks), taking the widest row as the file count
+pub fn board_dimensions(sfen: &str) -> (usize, usize) {
+    let board_part = sfen.split_whitespace().next().unwrap_or(sfen);
+    let rows = split_pocket(board_part).0;
+    let files = rows.iter().map(|row| parse_row(row).0).max().unwrap_or(0);
+    (files, rows.len())
+}
+
+/// Parse SFEN board position into a 2D vector of `ranks` rows by `files` columns
+///
+/// Short rows are padded with empty squares; crazyhouse pockets are skipped.
+pub fn parse_sfen(sfen: &str, files: usize, ranks: usize) -> Result<Vec<Vec<Option<Piece>>>, SfenError> {
+    let board_part = sfen.split_whitespace().next().unwrap_or(sfen);
+    let rows = split_pocket(board_part).0;
+
+    if rows.len() != ranks {
+        return Err(SfenError::RankCount {
+            expected: ranks,
+            found: rows.len(),
+        });
+    }
+
+    let mut board = vec![vec![None; files]; ranks];
+    for (row_idx, row) in rows.iter().enumerate() {
+        let (width, pieces) = parse_row(row);
+        if width > files {
+            return Err(SfenError::RowOverflow {
+                rank: row_idx + 1,
+                squares: width,
+                files,
+            });
+        }
+        for (col, piece) in pieces {
+            board[row_idx][col] = Some(piece);
+        }
+    }
+
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Piece keys of a parsed board, with `None` for empty squares
+    fn keys(board: &[Vec<Option<Piece>>]) -> Vec<Vec<Option<&str>>> {
+        board
+            .iter()
+            .map(|row| row.iter().map(|piece| piece.as_ref().map(|piece| piece.sfen_key.as_str())).collect())
+            .collect()
+    }
+
+    #[test]
+    fn multi_digit_empty_counts() {
+        let board = parse_sfen("12/12 b - 1", 12, 2).unwrap();
+        assert_eq!(keys(&board), vec![vec![None; 12]; 2]);
+
+        let board = parse_sfen("11P/P11", 12, 2).unwrap();
+        assert_eq!(board[0][11].as_ref().unwrap().sfen_key, "P");
+        assert_eq!(board[1][0].as_ref().unwrap().sfen_key, "P");
+    }
+
+    #[test]
+    fn overlong_row_is_an_error() {
+        let error = parse_sfen("13", 12, 1).unwrap_err();
+        assert!(matches!(error, SfenError::RowOverflow { rank: 1, squares: 13, files: 12 }));
+    }
+
+    #[test]
+    fn wrong_rank_count_is_an_error() {
+        let error = parse_sfen("9/9", 9, 3).unwrap_err();
+        assert!(matches!(error, SfenError::RankCount { expected: 3, found: 2 }));
+    }
+
+    #[test]
+    fn promotion_marker_and_empty_counts() {
+        // A digit after `+` cancels the promotion; a `+` after a digit keeps the pending count
+        let board = parse_sfen("+3P/3+P", 5, 2).unwrap();
+        assert_eq!(keys(&board)[0], [None, None, None, Some("P"), None]);
+        assert_eq!(keys(&board)[1], [None, None, None, Some("+P"), None]);
+    }
+
+    #[test]
+    fn short_rows_are_padded() {
+        let board = parse_sfen("3p/k", 9, 2).unwrap();
+        assert_eq!(board[0].len(), 9);
+        assert_eq!(keys(&board)[0][3], Some("p"));
+        assert!(board[0][4..].iter().all(Option::is_none));
+        assert_eq!(keys(&board)[1][0], Some("k"));
+    }
+
+    #[test]
+    fn parenthesized_codes() {
+        let board = parse_sfen("(DE)+(ln)1", 3, 1).unwrap();
+        assert_eq!(keys(&board)[0], [Some("DE"), Some("+ln"), None]);
+    }
+
+    #[test]
+    fn bracket_pockets() {
+        let empty = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1";
+        assert!(has_pockets(empty));
+        let hand = parse_pockets(empty);
+        assert!(hand.sente.is_empty() && hand.gote.is_empty());
+
+        let hand = parse_pockets("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[QnPn] w KQkq - 0 1");
+        assert_eq!(hand.sente, [("Q".to_string(), 1), ("P".to_string(), 1)]);
+        assert_eq!(hand.gote, [("n".to_string(), 2)]);
+    }
+
+    #[test]
+    fn ninth_row_pocket() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/Qn w KQkq - 0 1";
+        assert!(has_pockets(fen));
+        let hand = parse_pockets(fen);
+        assert_eq!(hand.sente, [("Q".to_string(), 1)]);
+        assert_eq!(hand.gote, [("n".to_string(), 1)]);
+        assert_eq!(board_dimensions(fen), (8, 8));
+        assert!(parse_sfen(fen, 8, 8).is_ok());
+    }
+
+    #[test]
+    fn no_pocket() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert!(!has_pockets(fen));
+        let hand = parse_pockets(fen);
+        assert!(hand.sente.is_empty() && hand.gote.is_empty());
+
+        // A ninth row on a 9x9 shogi board is a board row, not a pocket
+        assert!(!has_pockets("lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1"));
+    }
+
+    /// Rooks keeping castling rights in a FEN, parsed on its own board
+    fn castling_rooks(fen: &str) -> Vec<(usize, usize)> {
+        parse_castling(fen, &parse_sfen(fen, 8, 8).unwrap())
+    }
+
+    #[test]
+    fn standard_castling_rights() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(castling_rooks(start), [(7, 7), (7, 0), (0, 7), (0, 0)]);
+        assert!(castling_rooks("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1").is_empty());
+    }
+
+    #[test]
+    fn shredder_and_x_fen_castling_rights() {
+        // Chess960: king on b, rooks on a and h
+        let fen = "rkbqnbnr/pppppppp/8/8/8/8/PPPPPPPP/RKBQNBNR w HAha - 0 1";
+        assert_eq!(castling_rooks(fen), [(7, 7), (7, 0), (0, 7), (0, 0)]);
+
+        // X-FEN: K picks the outermost rook, so an inner rook needs its file letter
+        let fen = "4k3/8/8/8/8/8/8/R3KR1R w KF - 0 1";
+        assert_eq!(castling_rooks(fen), [(7, 7), (7, 5)]);
+    }
+
+    #[test]
+    fn castling_right_without_its_rook() {
+        let fen = "rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w KQkq - 0 1";
+        assert_eq!(castling_rooks(fen), [(7, 7), (0, 0)]);
+    }
 }
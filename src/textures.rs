@@ -0,0 +1,93 @@
+//! Piece image decoding and texture upload
+
+use std::fs;
+use std::path::Path;
+
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use resvg::{tiny_skia, usvg};
+
+/// Check if a path points to an SVG file
+pub fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+/// Parse an SVG file into a render tree
+pub fn load_svg(path: &Path) -> Option<usvg::Tree> {
+    let data = fs::read(path).ok()?;
+    usvg::Tree::from_data(&data, &usvg::Options::default()).ok()
+}
+
+/// Rasterize an SVG tree into a square image of `size` pixels, preserving aspect ratio
+pub fn rasterize_svg(tree: &usvg::Tree, size: u32) -> Option<ColorImage> {
+    let size = size.max(1);
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)?;
+
+    let svg_size = tree.size();
+    let scale = (size as f32 / svg_size.width()).min(size as f32 / svg_size.height());
+    let dx = (size as f32 - svg_size.width() * scale) / 2.0;
+    let dy = (size as f32 - svg_size.height() * scale) / 2.0;
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(dx, dy);
+
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    Some(ColorImage::from_rgba_premultiplied(
+        [size as usize, size as usize],
+        pixmap.data(),
+    ))
+}
+
+/// Cut the cell at (`row`, `col`) out of a sheet divided into `rows` x `cols` equal cells
+pub fn slice_sprite(
+    sheet: &image::DynamicImage,
+    rows: usize,
+    cols: usize,
+    row: usize,
+    col: usize,
+) -> Option<image::DynamicImage> {
+    if row >= rows || col >= cols {
+        return None;
+    }
+
+    let cell_width = sheet.width() / cols as u32;
+    let cell_height = sheet.height() / rows as u32;
+    if cell_width == 0 || cell_height == 0 {
+        return None;
+    }
+
+    Some(sheet.crop_imm(
+        col as u32 * cell_width,
+        row as u32 * cell_height,
+        cell_width,
+        cell_height,
+    ))
+}
+
+/// Rotate an image by 180 degrees
+pub fn rotate_180(image: &ColorImage) -> ColorImage {
+    let mut pixels = image.pixels.clone();
+    pixels.reverse();
+    ColorImage {
+        size: image.size,
+        pixels,
+    }
+}
+
+/// Convert a decoded raster image into a ColorImage
+pub fn image_to_color_image(img: image::DynamicImage) -> ColorImage {
+    let rgba = img.to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    let pixels = rgba.into_raw();
+    ColorImage::from_rgba_unmultiplied(size, &pixels)
+}
+
+/// Upload an image as a texture
+pub fn upload(ctx: &egui::Context, name: &str, image: ColorImage) -> TextureHandle {
+    ctx.load_texture(name, image, Default::default())
+}
+
+/// Upload an image as a texture that repeats outside the 0..1 UV range (for tiling)
+pub fn upload_repeating(ctx: &egui::Context, name: &str, image: ColorImage) -> TextureHandle {
+    ctx.load_texture(name, image, TextureOptions::LINEAR_REPEAT)
+}
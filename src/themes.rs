@@ -0,0 +1,18 @@
+//! Bundled color themes
+
+/// Built-in themes (name -> JSON with `shogi` and `chess` color sections)
+const BUILTIN_THEMES: [(&str, &str); 5] = [
+    ("classic", include_str!("../resources/themes/classic.json")),
+    ("wood", include_str!("../resources/themes/wood.json")),
+    ("dark", include_str!("../resources/themes/dark.json")),
+    ("high-contrast", include_str!("../resources/themes/high-contrast.json")),
+    ("lichess-blue", include_str!("../resources/themes/lichess-blue.json")),
+];
+
+/// Look up a built-in theme's JSON by name
+pub fn builtin_theme(name: &str) -> Option<&'static str> {
+    BUILTIN_THEMES
+        .iter()
+        .find(|(theme_name, _)| *theme_name == name)
+        .map(|(_, json)| *json)
+}
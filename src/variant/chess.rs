@@ -0,0 +1,217 @@
+//! Chess variants
+
+use super::{BoardStyle, Family, Notation, PieceStyle, Variant};
+use crate::piece_sets::{DEFAULT_CHESS_PIECES, MAKRUK_PIECES, SHATRANJ_PIECES};
+use crate::sfen::fen_to_unicode;
+
+/// A chess variant, drawn on a checkerboard unless it traditionally has plain squares
+pub struct ChessVariant {
+    id: &'static str,
+    name: &'static str,
+    files: usize,
+    ranks: usize,
+    piece_letters: &'static str,
+    labels: fn(&str) -> &'static str,
+    pieces: &'static [(&'static str, &'static [u8])],
+    board_style: BoardStyle,
+    has_hand: bool,
+    start_position: &'static str,
+    base_window_size: f32,
+}
+
+/// Standard chess (8x8)
+pub const CHESS: ChessVariant = ChessVariant {
+    id: "chess",
+    name: "Chess",
+    files: 8,
+    ranks: 8,
+    piece_letters: "KQRBNP",
+    labels: fen_to_unicode,
+    pieces: &DEFAULT_CHESS_PIECES,
+    board_style: BoardStyle::Checkerboard,
+    has_hand: false,
+    start_position: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    base_window_size: 700.0,
+};
+
+/// Chess960 (Fischer random), with X-FEN or Shredder-FEN castling rights
+pub const CHESS960: ChessVariant = ChessVariant {
+    id: "chess960",
+    name: "Chess960",
+    start_position: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1",
+    ..CHESS
+};
+
+/// Capablanca chess (10x8) with archbishop (`A`) and chancellor (`C`)
+pub const CAPABLANCA: ChessVariant = ChessVariant {
+    id: "capablanca",
+    name: "Capablanca Chess",
+    files: 10,
+    ranks: 8,
+    piece_letters: "KQRBNPAC",
+    labels: compound_symbols,
+    pieces: &DEFAULT_CHESS_PIECES,
+    board_style: BoardStyle::Checkerboard,
+    has_hand: false,
+    start_position: "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1",
+    base_window_size: 800.0,
+};
+
+/// Grand chess (10x10) with archbishop (`A`, cardinal) and chancellor (`C`, marshal)
+pub const GRAND: ChessVariant = ChessVariant {
+    id: "grand",
+    name: "Grand Chess",
+    files: 10,
+    ranks: 10,
+    piece_letters: "KQRBNPAC",
+    labels: compound_symbols,
+    pieces: &DEFAULT_CHESS_PIECES,
+    board_style: BoardStyle::Checkerboard,
+    has_hand: false,
+    start_position: "r8r/1nbqkcabn1/pppppppppp/10/10/10/10/PPPPPPPPPP/1NBQKCABN1/R8R w - - 0 1",
+    base_window_size: 800.0,
+};
+
+/// Crazyhouse: chess with captured pieces kept in pockets and dropped back
+pub const CRAZYHOUSE: ChessVariant = ChessVariant {
+    id: "crazyhouse",
+    name: "Crazyhouse",
+    files: 8,
+    ranks: 8,
+    piece_letters: "KQRBNP",
+    labels: fen_to_unicode,
+    pieces: &DEFAULT_CHESS_PIECES,
+    board_style: BoardStyle::Checkerboard,
+    has_hand: true,
+    start_position: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1",
+    base_window_size: 700.0,
+};
+
+/// Bughouse: one board of a crazyhouse pair, with pockets fed by the partner board
+pub const BUGHOUSE: ChessVariant = ChessVariant {
+    id: "bughouse",
+    name: "Bughouse",
+    ..CRAZYHOUSE
+};
+
+/// Makruk (Thai chess): met (`M`), khon (`S`), pawns on the third rank, plain squares
+pub const MAKRUK: ChessVariant = ChessVariant {
+    id: "makruk",
+    name: "Makruk",
+    files: 8,
+    ranks: 8,
+    piece_letters: "KMSNRP",
+    labels: makruk_symbols,
+    pieces: &MAKRUK_PIECES,
+    board_style: BoardStyle::Grid,
+    has_hand: false,
+    start_position: "rnsmksnr/8/pppppppp/8/8/PPPPPPPP/8/RNSKMSNR w - - 0 1",
+    base_window_size: 700.0,
+};
+
+/// Shatranj (medieval chess): ferz (`Q`/`F`) and alfil (`B`/`E`), plain squares
+pub const SHATRANJ: ChessVariant = ChessVariant {
+    id: "shatranj",
+    name: "Shatranj",
+    files: 8,
+    ranks: 8,
+    piece_letters: "KQFBENRP",
+    labels: shatranj_symbols,
+    pieces: &SHATRANJ_PIECES,
+    board_style: BoardStyle::Grid,
+    has_hand: false,
+    start_position: "rnbkqbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBKQBNR w - - 0 1",
+    base_window_size: 700.0,
+};
+
+/// Makruk pieces as the chess symbols of their closest counterparts
+fn makruk_symbols(fen_key: &str) -> &'static str {
+    match fen_key {
+        "M" => "♕",
+        "m" => "♛",
+        "S" => "♗",
+        "s" => "♝",
+        _ => fen_to_unicode(fen_key),
+    }
+}
+
+/// Capablanca and Grand chess compound pieces as their two component symbols, for piece
+/// sets without archbishop and chancellor images
+fn compound_symbols(fen_key: &str) -> &'static str {
+    match fen_key {
+        "A" => "♗♘",
+        "a" => "♝♞",
+        "C" => "♖♘",
+        "c" => "♜♞",
+        _ => fen_to_unicode(fen_key),
+    }
+}
+
+/// Shatranj pieces as the chess symbols of their descendants
+fn shatranj_symbols(fen_key: &str) -> &'static str {
+    match fen_key {
+        "F" => "♕",
+        "f" => "♛",
+        "E" => "♗",
+        "e" => "♝",
+        _ => fen_to_unicode(fen_key),
+    }
+}
+
+impl Variant for ChessVariant {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn family(&self) -> Family {
+        Family::Chess
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.files, self.ranks)
+    }
+
+    fn piece_letters(&self) -> &'static str {
+        self.piece_letters
+    }
+
+    fn piece_label(&self, key: &str) -> &'static str {
+        (self.labels)(key)
+    }
+
+    fn has_hand(&self) -> bool {
+        self.has_hand
+    }
+
+    fn start_position(&self) -> &'static str {
+        self.start_position
+    }
+
+    fn notation(&self) -> Notation {
+        Notation::Chess
+    }
+
+    fn board_style(&self) -> BoardStyle {
+        self.board_style
+    }
+
+    fn piece_style(&self) -> PieceStyle {
+        PieceStyle::Symbol
+    }
+
+    fn default_pieces(&self) -> &'static [(&'static str, &'static [u8])] {
+        self.pieces
+    }
+
+    fn base_window_size(&self) -> f32 {
+        self.base_window_size
+    }
+
+    fn coord_margin(&self) -> f32 {
+        0.10
+    }
+}
@@ -0,0 +1,292 @@
+//! Game variant definitions and detection
+
+mod chess;
+mod shogi;
+mod xiangqi;
+
+pub use chess::{BUGHOUSE, CAPABLANCA, CHESS, CHESS960, CRAZYHOUSE, GRAND, MAKRUK, SHATRANJ};
+pub use shogi::{
+    CHU_SHOGI, DOBUTSU_SHOGI, GORO_GORO_SHOGI, JUDKINS_SHOGI, KYOTO_SHOGI, MINISHOGI, STANDARD_SHOGI,
+};
+pub use xiangqi::{JANGGI, XIANGQI};
+
+use crate::sfen::{board_dimensions, has_pockets, parse_hand, parse_pockets, Hand};
+
+/// Game family, selecting the config section and default piece rendering
+#[derive(Clone, Copy, PartialEq)]
+pub enum Family {
+    Shogi,
+    Chess,
+    Xiangqi,
+}
+
+/// Coordinate notation family
+#[derive(Clone, Copy, PartialEq)]
+pub enum Notation {
+    /// Files numbered right to left, ranks top to bottom
+    Shogi,
+    /// Files lettered left to right, ranks numbered bottom to top
+    Chess,
+    /// Files numbered from each player's right, ranks numbered from 0 at the bottom
+    Xiangqi,
+    /// Files numbered left to right, ranks numbered from the top with the tenth as 0
+    Janggi,
+}
+
+/// How the board squares are drawn
+#[derive(Clone, Copy, PartialEq)]
+pub enum BoardStyle {
+    /// Uniform background with grid lines (shogi, makruk)
+    Grid,
+    /// Alternating light and dark squares (chess)
+    Checkerboard,
+    /// Lines through the square centers, pieces on the intersections (xiangqi)
+    Intersections,
+}
+
+/// How pieces are drawn when no image is configured
+#[derive(Clone, Copy, PartialEq)]
+pub enum PieceStyle {
+    /// Kanji in the bundled brush font, opponent pieces rotated 180°
+    Kanji,
+    /// Unicode symbols, never rotated
+    Symbol,
+    /// Characters on round discs colored by side, never rotated (xiangqi)
+    Disc,
+    /// Characters on octagonal discs sized by rank, colored by side, never rotated (janggi)
+    Octagon,
+}
+
+/// Colors of the two sides' disc pieces, uppercase side first
+#[derive(Clone, Copy, PartialEq)]
+pub enum SideColors {
+    RedBlack,
+    BlueRed,
+}
+
+/// A game variant: board geometry, pieces, rules relevant to display, and renderer hints
+pub trait Variant: Sync {
+    /// Identifier accepted by `--variant`
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name
+    fn name(&self) -> &'static str;
+
+    /// Game family (config section and defaults)
+    fn family(&self) -> Family;
+
+    /// Board size in squares as (files, ranks)
+    fn dimensions(&self) -> (usize, usize);
+
+    /// Uppercase letters of all pieces that may appear in a position
+    fn piece_letters(&self) -> &'static str;
+
+    /// Text label for a piece key ("?" if unknown)
+    fn piece_label(&self, key: &str) -> &'static str;
+
+    /// Canonical key for a parsed piece code (e.g. a multi-letter alias)
+    fn normalize_key(&self, key: &str) -> String {
+        key.to_string()
+    }
+
+    /// Whether captured pieces go to a hand and can be dropped
+    fn has_hand(&self) -> bool {
+        false
+    }
+
+    /// Number of ranks in each side's promotion zone (0 for none)
+    fn promotion_ranks(&self) -> usize {
+        0
+    }
+
+    /// Hoshi (star) points as (row, col) grid intersections
+    fn hoshi(&self) -> &'static [(usize, usize)] {
+        &[]
+    }
+
+    /// Whether the board is split by a river between its two halves
+    fn river(&self) -> bool {
+        false
+    }
+
+    /// Top-left (row, col) intersections of the 3x3 palaces with diagonals
+    fn palaces(&self) -> &'static [(usize, usize)] {
+        &[]
+    }
+
+    /// Starting position in SFEN/FEN notation
+    fn start_position(&self) -> &'static str;
+
+    /// Coordinate notation family
+    fn notation(&self) -> Notation;
+
+    /// How the board squares are drawn
+    fn board_style(&self) -> BoardStyle;
+
+    /// How pieces without images are drawn
+    fn piece_style(&self) -> PieceStyle;
+
+    /// Colors of disc and octagon pieces
+    fn side_colors(&self) -> SideColors {
+        SideColors::RedBlack
+    }
+
+    /// Bundled piece images (key -> encoded image bytes)
+    fn default_pieces(&self) -> &'static [(&'static str, &'static [u8])] {
+        &[]
+    }
+
+    /// Window width at scale 1.0
+    fn base_window_size(&self) -> f32;
+
+    /// Space reserved for coordinates on each side, as a fraction of the board area
+    fn coord_margin(&self) -> f32;
+}
+
+/// All known variants, in detection priority order
+static VARIANTS: [&dyn Variant; 17] = [
+    &STANDARD_SHOGI,
+    &MINISHOGI,
+    &KYOTO_SHOGI,
+    &JUDKINS_SHOGI,
+    &GORO_GORO_SHOGI,
+    &DOBUTSU_SHOGI,
+    &CHU_SHOGI,
+    &CHESS,
+    &CRAZYHOUSE,
+    &BUGHOUSE,
+    &CHESS960,
+    &CAPABLANCA,
+    &GRAND,
+    &MAKRUK,
+    &SHATRANJ,
+    &XIANGQI,
+    &JANGGI,
+];
+
+/// Find a variant by its `--variant` identifier
+pub fn find_variant(id: &str) -> Option<&'static dyn Variant> {
+    VARIANTS.iter().copied().find(|variant| variant.id().eq_ignore_ascii_case(id))
+}
+
+/// Find a variant by identifier or display name, ignoring case, spaces and punctuation
+///
+/// Accepts the names other tools write in headers, e.g. "Chu Shogi", "chushogi" or "Makruk".
+pub fn find_variant_by_name(name: &str) -> Option<&'static dyn Variant> {
+    let normalize = |text: &str| -> String {
+        text.chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .map(|ch| ch.to_ascii_lowercase())
+            .collect()
+    };
+    let wanted = normalize(name);
+    VARIANTS
+        .iter()
+        .copied()
+        .find(|variant| normalize(variant.id()) == wanted || normalize(variant.name()) == wanted)
+}
+
+/// Whether some known variant has a board of the given (files, ranks) and every piece
+/// letter in the board field (the same letter test `detect_variant` applies)
+pub fn is_known_board(board_part: &str, dimensions: (usize, usize)) -> bool {
+    let letters = board_letters(board_part);
+    VARIANTS.iter().any(|variant| {
+        variant.dimensions() == dimensions && letters.iter().all(|ch| variant.piece_letters().contains(*ch))
+    })
+}
+
+/// Uppercased piece letters of a board field
+fn board_letters(board_part: &str) -> Vec<char> {
+    board_part
+        .chars()
+        .filter(|ch| ch.is_ascii_alphabetic())
+        .map(|ch| ch.to_ascii_uppercase())
+        .collect()
+}
+
+/// Identifiers of all known variants
+pub fn variant_ids() -> Vec<&'static str> {
+    VARIANTS.iter().map(|variant| variant.id()).collect()
+}
+
+/// Detect the variant of a position from its dimensions and piece letters
+///
+/// The first variant whose dimensions match and whose piece set contains every
+/// piece letter wins (a chess variant with pockets, if the FEN has them); otherwise
+/// the first variant with matching dimensions, then the first with a matching rank
+/// count, falling back to standard shogi.
+pub fn detect_variant(sfen: &str) -> &'static dyn Variant {
+    let board_part = sfen.split_whitespace().next().unwrap_or(sfen);
+    let dimensions = board_dimensions(sfen);
+    let letters = board_letters(board_part);
+
+    let pockets = has_pockets(sfen);
+    let same_size = || VARIANTS.iter().copied().filter(|variant| variant.dimensions() == dimensions);
+
+    same_size()
+        .filter(|variant| !pockets || (variant.family() == Family::Chess && variant.has_hand()))
+        .find(|variant| letters.iter().all(|ch| variant.piece_letters().contains(*ch)))
+        .or_else(|| same_size().next())
+        .or_else(|| VARIANTS.iter().copied().find(|variant| variant.dimensions().1 == dimensions.1))
+        .unwrap_or(&STANDARD_SHOGI)
+}
+
+/// Pieces in hand: crazyhouse pockets for chess variants, the SFEN hand field otherwise
+pub fn parse_position_hand(variant: &dyn Variant, sfen: &str) -> Hand {
+    match variant.family() {
+        Family::Chess => parse_pockets(sfen),
+        Family::Shogi | Family::Xiangqi => parse_hand(sfen),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_positions_detect_their_variant() {
+        // Variants sharing a board and letters with an earlier one are only reached via a hint
+        let expected = [
+            ("shogi", "shogi"),
+            ("minishogi", "minishogi"),
+            ("kyoto", "kyoto"),
+            ("judkins", "judkins"),
+            ("gorogoro", "gorogoro"),
+            ("dobutsu", "dobutsu"),
+            ("chu", "chu"),
+            ("chess", "chess"),
+            ("crazyhouse", "crazyhouse"),
+            ("bughouse", "crazyhouse"),
+            ("chess960", "chess"),
+            ("capablanca", "capablanca"),
+            ("grand", "grand"),
+            ("makruk", "makruk"),
+            ("shatranj", "chess"),
+            ("xiangqi", "xiangqi"),
+            ("janggi", "xiangqi"),
+        ];
+        assert_eq!(expected.len(), VARIANTS.len());
+        for (id, detected) in expected {
+            let variant = find_variant(id).unwrap();
+            assert_eq!(detect_variant(variant.start_position()).id(), detected, "{}", id);
+        }
+    }
+
+    #[test]
+    fn pockets_select_a_drop_chess_variant() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[Qn] w KQkq - 0 1";
+        assert_eq!(detect_variant(fen).id(), "crazyhouse");
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/Qn w KQkq - 0 1";
+        assert_eq!(detect_variant(fen).id(), "crazyhouse");
+    }
+
+    #[test]
+    fn fallbacks() {
+        // Same size with unknown letters: the first variant of that size
+        assert_eq!(detect_variant("zzzzzzzzz/9/9/9/9/9/9/9/9 b - 1").id(), "shogi");
+        // No variant of that size: the first with the same rank count
+        assert_eq!(detect_variant("7/7/7/7/7/7/7/7 w - - 0 1").id(), "chess");
+        // Nothing matches: standard shogi
+        assert_eq!(detect_variant("7/7/7").id(), "shogi");
+    }
+}
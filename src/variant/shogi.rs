@@ -0,0 +1,312 @@
+//! Shogi variants
+
+use super::{BoardStyle, Family, Notation, PieceStyle, Variant};
+use crate::sfen::sfen_to_kanji;
+
+/// A shogi variant drawn on a grid with kanji pieces
+pub struct ShogiVariant {
+    id: &'static str,
+    name: &'static str,
+    files: usize,
+    ranks: usize,
+    piece_letters: &'static str,
+    labels: fn(&str) -> &'static str,
+    aliases: &'static [(&'static str, &'static str)],
+    has_hand: bool,
+    promotion_ranks: usize,
+    hoshi: &'static [(usize, usize)],
+    start_position: &'static str,
+    base_window_size: f32,
+    coord_margin: f32,
+}
+
+/// Standard shogi (9x9)
+pub const STANDARD_SHOGI: ShogiVariant = ShogiVariant {
+    id: "shogi",
+    name: "Shogi",
+    files: 9,
+    ranks: 9,
+    piece_letters: "KRBGSNLP",
+    labels: sfen_to_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 3,
+    hoshi: &[(3, 3), (3, 6), (6, 3), (6, 6)],
+    start_position: "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1",
+    base_window_size: 900.0,
+    coord_margin: 0.05,
+};
+
+/// Minishogi (5x5), no hoshi
+pub const MINISHOGI: ShogiVariant = ShogiVariant {
+    id: "minishogi",
+    name: "Minishogi",
+    files: 5,
+    ranks: 5,
+    piece_letters: "KRBGSP",
+    labels: sfen_to_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 1,
+    hoshi: &[],
+    start_position: "rbsgk/4p/5/P4/KGSBR b - 1",
+    base_window_size: 500.0,
+    coord_margin: 0.10,
+};
+
+/// Judkin's shogi (6x6)
+pub const JUDKINS_SHOGI: ShogiVariant = ShogiVariant {
+    id: "judkins",
+    name: "Judkin's Shogi",
+    files: 6,
+    ranks: 6,
+    piece_letters: "KRBGSNP",
+    labels: sfen_to_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 2,
+    hoshi: &[],
+    start_position: "rbnsgk/5p/6/6/P5/KGSNBR b - 1",
+    base_window_size: 600.0,
+    coord_margin: 0.10,
+};
+
+/// Kyoto shogi (5x5), where every piece flips between two kinds after each move
+pub const KYOTO_SHOGI: ShogiVariant = ShogiVariant {
+    id: "kyoto",
+    name: "Kyoto Shogi",
+    files: 5,
+    ranks: 5,
+    piece_letters: "KSNLP",
+    labels: kyoto_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 0,
+    hoshi: &[],
+    start_position: "p+nks+l/5/5/5/+LSK+NP b - 1",
+    base_window_size: 500.0,
+    coord_margin: 0.10,
+};
+
+/// Goro Goro shogi (5 files x 6 ranks)
+pub const GORO_GORO_SHOGI: ShogiVariant = ShogiVariant {
+    id: "gorogoro",
+    name: "Goro Goro Shogi",
+    files: 5,
+    ranks: 6,
+    piece_letters: "KGSNLP",
+    labels: sfen_to_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 2,
+    hoshi: &[],
+    start_position: "sgkgs/5/1ppp1/1PPP1/5/SGKGS b - 1",
+    base_window_size: 500.0,
+    coord_margin: 0.10,
+};
+
+/// Dobutsu shogi (3 files x 4 ranks) with lion, giraffe, elephant and chick
+pub const DOBUTSU_SHOGI: ShogiVariant = ShogiVariant {
+    id: "dobutsu",
+    name: "Dobutsu Shogi",
+    files: 3,
+    ranks: 4,
+    piece_letters: "LGEC",
+    labels: dobutsu_kanji,
+    aliases: &[],
+    has_hand: true,
+    promotion_ranks: 1,
+    hoshi: &[],
+    start_position: "gle/1c1/1C1/ELG b - 1",
+    base_window_size: 400.0,
+    coord_margin: 0.10,
+};
+
+/// Chu shogi (12x12), no drops; hoshi after the 4th and 8th ranks
+pub const CHU_SHOGI: ShogiVariant = ShogiVariant {
+    id: "chu",
+    name: "Chu Shogi",
+    files: 12,
+    ranks: 12,
+    piece_letters: "LFCSGEKABTXOMVRHDQNPI",
+    labels: chu_kanji,
+    aliases: CHU_ALIASES,
+    has_hand: false,
+    promotion_ranks: 4,
+    hoshi: &[(4, 4), (4, 8), (8, 4), (8, 8)],
+    start_position: "lfcsgekgscfl/a1b1txot1b1a/mvrhdqndhrvm/pppppppppppp/3i4i3/12/12/3I4I3/PPPPPPPPPPPP/MVRHDNQDHRVM/A1B1TOXT1B1A/LFCSGKEGSCFL b - 1",
+    base_window_size: 1000.0,
+    coord_margin: 0.04,
+};
+
+/// Common multi-letter Chu shogi abbreviations and their single-letter keys
+const CHU_ALIASES: &[(&str, &str)] = &[
+    ("FL", "F"),
+    ("DE", "E"),
+    ("RC", "A"),
+    ("BT", "T"),
+    ("KR", "X"),
+    ("PH", "O"),
+    ("SM", "M"),
+    ("VM", "V"),
+    ("DH", "H"),
+    ("DK", "D"),
+    ("FK", "Q"),
+    ("LN", "N"),
+    ("GB", "I"),
+];
+
+/// Chu shogi pieces, using the usual one-kanji abbreviations
+fn chu_kanji(sfen_key: &str) -> &'static str {
+    match sfen_key {
+        "L" | "l" => "香",
+        "+L" | "+l" => "駒",
+        "F" | "f" => "豹",
+        "+F" | "+f" => "角",
+        "C" | "c" => "銅",
+        "+C" | "+c" => "横",
+        "S" | "s" => "銀",
+        "+S" | "+s" => "竪",
+        "G" | "g" => "金",
+        "+G" | "+g" => "飛",
+        "E" | "e" => "象",
+        "+E" | "+e" => "太",
+        "K" | "k" => "玉",
+        "A" | "a" => "反",
+        "+A" | "+a" => "鯨",
+        "B" | "b" => "角",
+        "+B" | "+b" => "馬",
+        "T" | "t" => "虎",
+        "+T" | "+t" => "鹿",
+        "X" | "x" => "麒",
+        "+X" | "+x" => "獅",
+        "O" | "o" => "鳳",
+        "+O" | "+o" => "奔",
+        "M" | "m" => "横",
+        "+M" | "+m" => "猪",
+        "V" | "v" => "竪",
+        "+V" | "+v" => "牛",
+        "R" | "r" => "飛",
+        "+R" | "+r" => "龍",
+        "H" | "h" => "馬",
+        "+H" | "+h" => "鷹",
+        "D" | "d" => "龍",
+        "+D" | "+d" => "鷲",
+        "Q" | "q" => "奔",
+        "N" | "n" => "獅",
+        "P" | "p" => "歩",
+        "+P" | "+p" => "と",
+        "I" | "i" => "仲",
+        "+I" | "+i" => "象",
+        _ => "?",
+    }
+}
+
+/// Kyoto shogi pieces: each letter is one side of a flipping piece, `+` the other side
+fn kyoto_kanji(sfen_key: &str) -> &'static str {
+    match sfen_key {
+        "K" | "k" => "玉",
+        "P" | "p" => "歩",
+        "+P" | "+p" => "飛",
+        "S" | "s" => "銀",
+        "+S" | "+s" => "角",
+        "N" | "n" => "桂",
+        "+N" | "+n" => "金",
+        "L" | "l" => "香",
+        "+L" | "+l" => "と",
+        _ => "?",
+    }
+}
+
+/// Dobutsu shogi pieces (the chick promotes to a hen)
+fn dobutsu_kanji(sfen_key: &str) -> &'static str {
+    match sfen_key {
+        "L" | "l" => "獅",
+        "G" | "g" => "麒",
+        "E" | "e" => "象",
+        "C" | "c" => "雛",
+        "+C" | "+c" => "鶏",
+        _ => "?",
+    }
+}
+
+impl Variant for ShogiVariant {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn family(&self) -> Family {
+        Family::Shogi
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.files, self.ranks)
+    }
+
+    fn piece_letters(&self) -> &'static str {
+        self.piece_letters
+    }
+
+    fn piece_label(&self, key: &str) -> &'static str {
+        (self.labels)(key)
+    }
+
+    fn normalize_key(&self, key: &str) -> String {
+        let (prefix, code) = match key.strip_prefix('+') {
+            Some(code) => ("+", code),
+            None => ("", key),
+        };
+        let is_gote_code = code.chars().all(|ch| !ch.is_alphabetic() || ch.is_lowercase());
+        let canonical = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(code))
+            .map(|(_, canonical)| *canonical);
+
+        match canonical {
+            Some(canonical) if is_gote_code => format!("{}{}", prefix, canonical.to_lowercase()),
+            Some(canonical) => format!("{}{}", prefix, canonical),
+            None => key.to_string(),
+        }
+    }
+
+    fn has_hand(&self) -> bool {
+        self.has_hand
+    }
+
+    fn promotion_ranks(&self) -> usize {
+        self.promotion_ranks
+    }
+
+    fn hoshi(&self) -> &'static [(usize, usize)] {
+        self.hoshi
+    }
+
+    fn start_position(&self) -> &'static str {
+        self.start_position
+    }
+
+    fn notation(&self) -> Notation {
+        Notation::Shogi
+    }
+
+    fn board_style(&self) -> BoardStyle {
+        BoardStyle::Grid
+    }
+
+    fn piece_style(&self) -> PieceStyle {
+        PieceStyle::Kanji
+    }
+
+    fn base_window_size(&self) -> f32 {
+        self.base_window_size
+    }
+
+    fn coord_margin(&self) -> f32 {
+        self.coord_margin
+    }
+}
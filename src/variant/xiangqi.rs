@@ -0,0 +1,141 @@
+//! Xiangqi-style variants, with pieces on the grid intersections
+
+use super::{BoardStyle, Family, Notation, PieceStyle, SideColors, Variant};
+
+/// A variant played on the intersections of a 9x10 board with palaces
+pub struct XiangqiVariant {
+    id: &'static str,
+    name: &'static str,
+    piece_letters: &'static str,
+    labels: fn(&str) -> &'static str,
+    river: bool,
+    notation: Notation,
+    piece_style: PieceStyle,
+    side_colors: SideColors,
+    start_position: &'static str,
+}
+
+/// Xiangqi (Chinese chess), red (uppercase) at the bottom
+pub const XIANGQI: XiangqiVariant = XiangqiVariant {
+    id: "xiangqi",
+    name: "Xiangqi",
+    piece_letters: "KABENHRCP",
+    labels: xiangqi_characters,
+    river: true,
+    notation: Notation::Xiangqi,
+    piece_style: PieceStyle::Disc,
+    side_colors: SideColors::RedBlack,
+    start_position: "rnbakabnr/9/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/9/RNBAKABNR w - - 0 1",
+};
+
+/// Janggi (Korean chess), Cho (blue, uppercase) at the bottom and Han (red) at the top
+pub const JANGGI: XiangqiVariant = XiangqiVariant {
+    id: "janggi",
+    name: "Janggi",
+    piece_letters: "KABENHRCP",
+    labels: janggi_hanja,
+    river: false,
+    notation: Notation::Janggi,
+    piece_style: PieceStyle::Octagon,
+    side_colors: SideColors::BlueRed,
+    start_position: "rnba1abnr/4k4/1c5c1/p1p1p1p1p/9/9/P1P1P1P1P/1C5C1/4K4/RNBA1ABNR w - - 0 1",
+};
+
+/// Xiangqi pieces: red uses the traditional red characters, black the black ones
+///
+/// Both `B`/`E` (elephant) and `N`/`H` (horse) are accepted, as FEN dialects differ.
+fn xiangqi_characters(fen_key: &str) -> &'static str {
+    match fen_key {
+        "K" => "帥",
+        "k" => "將",
+        "A" => "仕",
+        "a" => "士",
+        "B" | "E" => "相",
+        "b" | "e" => "象",
+        "N" | "H" | "n" | "h" => "馬",
+        "R" | "r" => "車",
+        "C" => "炮",
+        "c" => "砲",
+        "P" => "兵",
+        "p" => "卒",
+        _ => "?",
+    }
+}
+
+/// Janggi pieces in Hanja: Cho's general is 楚 and Han's is 漢, as are the soldiers' characters
+fn janggi_hanja(fen_key: &str) -> &'static str {
+    match fen_key {
+        "K" => "楚",
+        "k" => "漢",
+        "A" | "a" => "士",
+        "B" | "E" | "b" | "e" => "象",
+        "N" | "H" | "n" | "h" => "馬",
+        "R" | "r" => "車",
+        "C" | "c" => "包",
+        "P" => "卒",
+        "p" => "兵",
+        _ => "?",
+    }
+}
+
+impl Variant for XiangqiVariant {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn family(&self) -> Family {
+        Family::Xiangqi
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (9, 10)
+    }
+
+    fn piece_letters(&self) -> &'static str {
+        self.piece_letters
+    }
+
+    fn piece_label(&self, key: &str) -> &'static str {
+        (self.labels)(key)
+    }
+
+    fn river(&self) -> bool {
+        self.river
+    }
+
+    fn palaces(&self) -> &'static [(usize, usize)] {
+        &[(0, 3), (7, 3)]
+    }
+
+    fn start_position(&self) -> &'static str {
+        self.start_position
+    }
+
+    fn notation(&self) -> Notation {
+        self.notation
+    }
+
+    fn board_style(&self) -> BoardStyle {
+        BoardStyle::Intersections
+    }
+
+    fn piece_style(&self) -> PieceStyle {
+        self.piece_style
+    }
+
+    fn side_colors(&self) -> SideColors {
+        self.side_colors
+    }
+
+    fn base_window_size(&self) -> f32 {
+        700.0
+    }
+
+    fn coord_margin(&self) -> f32 {
+        0.06
+    }
+}